@@ -0,0 +1,9 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use TEMPLATE_NAME::{TEMPLATE_STRUCT, EMBEDDED_INPUT};
+
+fn solution(c: &mut Criterion) {
+    common::bench::solution::<TEMPLATE_STRUCT>(c, env!("CARGO_MANIFEST_DIR"), EMBEDDED_INPUT);
+}
+
+criterion_group!(benches, solution);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use common::{parse::lines, ParseError, Solution};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Input = Vec<u32>;
+
+pub fn parse(s: &str) -> Result<Input, ParseError> {
+    lines(s).map(|l| l.parse(l.text, "a number")).collect()
+}
+
+pub fn answer_part1(_inputs: &Input) -> usize {
+    todo!()
+}
+
+pub fn answer_part2(_inputs: &Input) -> usize {
+    todo!()
+}
+
+pub struct TEMPLATE_STRUCT;
+
+impl Solution for TEMPLATE_STRUCT {
+    const DAY: u8 = TEMPLATE_DAY;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example");
+
+    #[test]
+    fn _part1() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(0, answer_part1(&inputs));
+    }
+    #[test]
+    fn _part2() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(0, answer_part2(&inputs));
+    }
+}
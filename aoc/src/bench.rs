@@ -0,0 +1,267 @@
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How long a single sample runs for; fast stages are repeated until they fill it.
+const SAMPLE_TIME: Duration = Duration::from_millis(2);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        })
+    }
+}
+
+/// Per-iteration timings in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub median_ns: f64,
+    pub min_ns: f64,
+    pub stddev_ns: f64,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<f64>) -> Self {
+        assert!(!samples.is_empty(), "requires 1 or more samples");
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+        Self {
+            median_ns: median,
+            min_ns: samples[0],
+            stddev_ns: variance.sqrt(),
+        }
+    }
+}
+
+pub struct Sampler {
+    pub samples: usize,
+}
+
+impl Sampler {
+    pub fn measure<T>(&self, mut f: impl FnMut() -> T) -> Stats {
+        // The first call warms caches and tells us how many iterations fill a sample.
+        let start = Instant::now();
+        std::hint::black_box(f());
+        let first = start.elapsed();
+        let iterations = (SAMPLE_TIME.as_nanos() / first.as_nanos().max(1)).max(1) as u32;
+
+        let samples = (0..self.samples.max(1))
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..iterations {
+                    std::hint::black_box(f());
+                }
+                start.elapsed().as_nanos() as f64 / iterations as f64
+            })
+            .collect();
+        Stats::from_samples(samples)
+    }
+}
+
+pub struct Timing {
+    pub stage: Stage,
+    /// The measurements, or the panic message if the stage panicked.
+    pub stats: Result<Stats, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub entries: Vec<Entry>,
+    /// Stages that panicked, with their message.
+    #[serde(skip)]
+    pub failures: Vec<(u8, Stage, String)>,
+}
+
+impl Report {
+    pub fn push(&mut self, day: u8, timing: Timing) {
+        match timing.stats {
+            Ok(stats) => self.entries.push(Entry {
+                day,
+                stage: timing.stage,
+                stats,
+            }),
+            Err(message) => self.failures.push((day, timing.stage, message)),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        serde_json::from_str(&s).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")
+    }
+
+    fn find(&self, day: u8, stage: Stage) -> Option<&Stats> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.stage == stage)
+            .map(|e| &e.stats)
+    }
+
+    /// Entries whose median grew by more than `threshold` (a fraction) over the baseline.
+    pub fn regressions<'a>(&'a self, baseline: &Report, threshold: f64) -> Vec<&'a Entry> {
+        self.entries
+            .iter()
+            .filter(|e| {
+                baseline
+                    .find(e.day, e.stage)
+                    .is_some_and(|b| e.stats.median_ns > b.median_ns * (1.0 + threshold))
+            })
+            .collect()
+    }
+
+    pub fn to_markdown(&self, baseline: Option<&Report>, threshold: f64) -> String {
+        let mut s = String::new();
+        s.push_str("| Day | Stage | Median | Min | Stddev |");
+        if baseline.is_some() {
+            s.push_str(" Baseline | Change |");
+        }
+        s.push('\n');
+        s.push_str("|----:|-------|-------:|----:|-------:|");
+        if baseline.is_some() {
+            s.push_str("---------:|-------:|");
+        }
+        s.push('\n');
+
+        for e in &self.entries {
+            let _ = write!(
+                s,
+                "| {} | {} | {} | {} | {} |",
+                e.day,
+                e.stage,
+                format_ns(e.stats.median_ns),
+                format_ns(e.stats.min_ns),
+                format_ns(e.stats.stddev_ns),
+            );
+            if let Some(baseline) = baseline {
+                match baseline.find(e.day, e.stage) {
+                    Some(b) => {
+                        let change = e.stats.median_ns / b.median_ns - 1.0;
+                        let change = format!("{:+.1}%", change * 100.0);
+                        if e.stats.median_ns > b.median_ns * (1.0 + threshold) {
+                            let _ = write!(
+                                s,
+                                " {} | **{} regressed** |",
+                                format_ns(b.median_ns),
+                                change
+                            );
+                        } else {
+                            let _ = write!(s, " {} | {} |", format_ns(b.median_ns), change);
+                        }
+                    }
+                    None => s.push_str(" - | new |"),
+                }
+            }
+            s.push('\n');
+        }
+        for (day, stage, message) in &self.failures {
+            let _ = write!(s, "| {} | {} | panicked: {} | | |", day, stage, message);
+            if baseline.is_some() {
+                s.push_str(" | |");
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+fn format_ns(ns: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns.round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, stage: Stage, median_ns: f64) -> Entry {
+        Entry {
+            day,
+            stage,
+            stats: Stats {
+                median_ns,
+                min_ns: median_ns,
+                stddev_ns: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn _stats() {
+        let stats = Stats::from_samples(vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(2.5, stats.median_ns);
+        assert_eq!(1.0, stats.min_ns);
+        assert_eq!(1.25f64.sqrt(), stats.stddev_ns);
+    }
+
+    #[test]
+    fn _regressions() {
+        let baseline = Report {
+            entries: vec![entry(1, Stage::Parse, 100.0), entry(1, Stage::Part1, 100.0)],
+            ..Report::default()
+        };
+        let report = Report {
+            entries: vec![
+                entry(1, Stage::Parse, 105.0),
+                entry(1, Stage::Part1, 150.0),
+                entry(2, Stage::Parse, 1000.0),
+            ],
+            ..Report::default()
+        };
+        let regressed = report.regressions(&baseline, 0.1);
+        assert_eq!(vec![&entry(1, Stage::Part1, 150.0)], regressed);
+        assert!(report.to_markdown(Some(&baseline), 0.1).contains(
+            "| 1 | part1 | 150.00ns | 150.00ns | 0.00ns | 100.00ns | **+50.0% regressed** |"
+        ));
+    }
+
+    #[test]
+    fn _json_round_trip() {
+        let report = Report {
+            entries: vec![entry(3, Stage::Part2, 42.0)],
+            ..Report::default()
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            r#"{"entries":[{"day":3,"stage":"part2","median_ns":42.0,"min_ns":42.0,"stddev_ns":0.0}]}"#,
+            json
+        );
+        assert_eq!(
+            report.entries,
+            serde_json::from_str::<Report>(&json).unwrap().entries
+        );
+    }
+}
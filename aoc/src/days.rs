@@ -0,0 +1,118 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use common::{ParseError, Solution};
+
+use crate::bench::{Sampler, Stage, Stats, Timing};
+
+pub struct Day {
+    pub number: u8,
+    solve: fn(&str, Option<u8>) -> Result<Vec<Answer>, ParseError>,
+    time: fn(&str, &Sampler) -> Result<Vec<Timing>, ParseError>,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            solve: solve::<S>,
+            time: time::<S>,
+        }
+    }
+
+    /// Parses `input` and answers the requested part, or both parts if `part` is `None`.
+    pub fn solve(&self, input: &str, part: Option<u8>) -> Result<Vec<Answer>, ParseError> {
+        (self.solve)(input, part)
+    }
+
+    /// Measures parsing `input` and answering each part from the parsed input.
+    pub fn time(&self, input: &str, sampler: &Sampler) -> Result<Vec<Timing>, ParseError> {
+        (self.time)(input, sampler)
+    }
+}
+
+pub struct Answer {
+    pub part: u8,
+    /// The answer, or the panic message if the part panicked.
+    pub value: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+fn answer(part: u8, f: impl FnOnce() -> String) -> Answer {
+    let start = Instant::now();
+    let value = catch(f);
+    Answer {
+        part,
+        value,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Runs `f`, turning a panic into its message. The default hook is silenced meanwhile, so a
+/// panicking part shows up in the table like any other answer rather than as a backtrace.
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    result.map_err(panic_message)
+}
+
+fn panic_message(e: Box<dyn Any + Send>) -> String {
+    match e.downcast::<String>() {
+        Ok(s) => *s,
+        Err(e) => e.downcast_ref::<&str>().unwrap_or(&"panicked").to_string(),
+    }
+}
+
+fn solve<S: Solution>(input: &str, part: Option<u8>) -> Result<Vec<Answer>, ParseError> {
+    let inputs = S::parse(input)?;
+    let mut answers = Vec::new();
+    if part.unwrap_or(1) == 1 {
+        answers.push(answer(1, || S::part1(&inputs).to_string()));
+    }
+    if part.unwrap_or(2) == 2 {
+        answers.push(answer(2, || S::part2(&inputs).to_string()));
+    }
+    Ok(answers)
+}
+
+fn timing(stage: Stage, f: impl FnOnce() -> Stats) -> Timing {
+    Timing {
+        stage,
+        stats: catch(f),
+    }
+}
+
+fn time<S: Solution>(input: &str, sampler: &Sampler) -> Result<Vec<Timing>, ParseError> {
+    let inputs = S::parse(input)?;
+    Ok(vec![
+        timing(Stage::Parse, || sampler.measure(|| S::parse(input))),
+        timing(Stage::Part1, || sampler.measure(|| S::part1(&inputs))),
+        timing(Stage::Part2, || sampler.measure(|| S::part2(&inputs))),
+    ])
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day1::Day1>(),
+    Day::of::<day2::Day2>(),
+    Day::of::<day3::Day3>(),
+    Day::of::<day4::Day4>(),
+    Day::of::<day5::Day5>(),
+    Day::of::<day6::Day6>(),
+    Day::of::<day7::Day7>(),
+    Day::of::<day8::Day8>(),
+    Day::of::<day9::Day9>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day13::Day13>(),
+    Day::of::<day14::Day14>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
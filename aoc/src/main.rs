@@ -0,0 +1,323 @@
+mod bench;
+mod days;
+mod scaffold;
+mod verify;
+
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    time::Instant,
+};
+
+use bench::{Report, Sampler};
+use clap::{Parser, Subcommand};
+use days::{Answer, Day};
+use verify::{Answers, Verdict};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or every day
+    Run {
+        /// Day number, or `all`
+        day: Selection,
+        /// Only answer this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Input file, `-` for stdin. Defaults to the day's `src/inputs`
+        #[arg(long, conflicts_with = "verify")]
+        input: Option<PathBuf>,
+        /// Compare the answers against the day's `src/answers.toml` and fail on a mismatch
+        #[arg(long)]
+        verify: bool,
+    },
+    /// Time parsing and both parts, and print a Markdown table of the results
+    Bench {
+        /// Day number, or `all`
+        #[arg(default_value = "all")]
+        day: Selection,
+        /// Number of samples taken per stage
+        #[arg(long, default_value_t = 20)]
+        samples: usize,
+        /// Also write the results as JSON to this file
+        #[arg(long)]
+        json: Option<PathBuf>,
+        /// Compare against a JSON report written by an earlier run
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Write the results as JSON to this file for later runs to compare against
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+        /// Slowdown of the median, in percent, that counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Create a crate for a new day from `_template` and add it to the workspace and the runner
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Selection {
+    All,
+    Day(&'static Day),
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Selection::All);
+        }
+        s.parse::<u8>()
+            .ok()
+            .and_then(days::find)
+            .map(Selection::Day)
+            .ok_or_else(|| {
+                let last = days::DAYS.last().map_or(0, |d| d.number);
+                format!("expected `all` or a day between 1 and {}", last)
+            })
+    }
+}
+
+fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner lives inside the workspace")
+}
+
+fn day_dir(number: u8) -> PathBuf {
+    workspace_dir().join(format!("day{}", number))
+}
+
+fn print_answer(day: u8, answer: &Answer, verdict: Option<&Verdict>) {
+    let value = answer.value.as_deref().unwrap_or("");
+    let status = match (&answer.value, verdict) {
+        (Err(message), _) => format!(" panicked: {}", message),
+        (Ok(_), None) => String::new(),
+        (Ok(_), Some(Verdict::Correct)) => " ok".to_string(),
+        (Ok(_), Some(Verdict::Unrecorded)) => " (no recorded answer)".to_string(),
+        (Ok(_), Some(Verdict::Wrong { expected })) if expected.contains('\n') => {
+            format!(" MISMATCH, expected\n{}\nbut got", expected.trim_end())
+        }
+        (Ok(_), Some(Verdict::Wrong { expected })) => format!(" MISMATCH, expected {}", expected),
+    };
+    if value.contains('\n') {
+        println!(
+            "Day {:>2} part {} = ({:.1?}){}",
+            day, answer.part, answer.elapsed, status
+        );
+        println!("{}", value.trim_end());
+    } else {
+        println!(
+            "Day {:>2} part {} = {} ({:.1?}){}",
+            day, answer.part, value, answer.elapsed, status
+        );
+    }
+}
+
+fn run(selection: Selection, part: Option<u8>, input: Option<PathBuf>, verify: bool) -> ExitCode {
+    let days = selected(selection);
+    if input.is_some() && days.len() > 1 {
+        eprintln!("--input can only be used when running a single day");
+        return ExitCode::FAILURE;
+    }
+
+    let start = Instant::now();
+    let mut failed = false;
+    let mut regressions = vec![];
+    let mut answered = 0;
+    for day in days {
+        let input = match &input {
+            Some(path) => common::input::from_path(path),
+            None => common::input::from_crate(day_dir(day.number), None),
+        };
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {:>2}: failed to read input: {}", day.number, e);
+                failed = true;
+                continue;
+            }
+        };
+        let recorded = if verify {
+            match Answers::load(&day_dir(day.number)) {
+                Ok(answers) => Some(answers),
+                Err(e) => {
+                    eprintln!("Day {:>2}: failed to read answers: {}", day.number, e);
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+        let answers = match day.solve(&input, part) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Day {:>2}: failed to parse input at {}", day.number, e);
+                failed = true;
+                continue;
+            }
+        };
+        for answer in answers {
+            let verdict = match (&recorded, &answer.value) {
+                (Some(recorded), Ok(value)) => {
+                    Some(verify::check(recorded.expected(answer.part), value))
+                }
+                _ => None,
+            };
+            if answer.value.is_err() || matches!(verdict, Some(Verdict::Wrong { .. })) {
+                regressions.push((day.number, answer.part));
+            }
+            print_answer(day.number, &answer, verdict.as_ref());
+            answered += 1;
+        }
+    }
+    println!("Answered {} parts in {:.1?}", answered, start.elapsed());
+
+    if !regressions.is_empty() {
+        eprintln!("Failed:");
+        for (day, part) in regressions {
+            eprintln!("  day {} part {}", day, part);
+        }
+        failed = true;
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn selected(selection: Selection) -> Vec<&'static Day> {
+    match selection {
+        Selection::All => days::DAYS.iter().collect(),
+        Selection::Day(day) => vec![day],
+    }
+}
+
+struct BenchOptions {
+    samples: usize,
+    json: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn bench(selection: Selection, options: BenchOptions) -> ExitCode {
+    let baseline = match options.baseline.as_deref().map(Report::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("failed to read baseline: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let threshold = options.threshold / 100.0;
+    let sampler = Sampler {
+        samples: options.samples,
+    };
+
+    let mut failed = false;
+    let mut report = Report::default();
+    for day in selected(selection) {
+        eprintln!("Timing day {}", day.number);
+        let timings = common::input::from_crate(day_dir(day.number), None)
+            .map_err(|e| format!("failed to read input: {}", e))
+            .and_then(|input| {
+                day.time(&input, &sampler)
+                    .map_err(|e| format!("failed to parse input at {}", e))
+            });
+        match timings {
+            Ok(timings) => timings.into_iter().for_each(|t| report.push(day.number, t)),
+            Err(e) => {
+                eprintln!("Day {:>2}: {}", day.number, e);
+                failed = true;
+            }
+        }
+    }
+
+    print!("{}", report.to_markdown(baseline.as_ref(), threshold));
+    for path in [&options.json, &options.save_baseline]
+        .into_iter()
+        .flatten()
+    {
+        if let Err(e) = report.save(path) {
+            eprintln!("failed to write {}: {}", path.display(), e);
+            failed = true;
+        }
+    }
+    if let Some(baseline) = &baseline {
+        let regressions = report.regressions(baseline, threshold);
+        if !regressions.is_empty() {
+            eprintln!("Regressed against the baseline:");
+            for e in regressions {
+                eprintln!("  day {} {}", e.day, e.stage);
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn new_day(day: u8) -> ExitCode {
+    match scaffold::new_day(workspace_dir(), day) {
+        Ok(written) => {
+            for path in written {
+                let path = path.strip_prefix(workspace_dir()).unwrap_or(&path);
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "Paste the puzzle input into day{0}/src/inputs and the example into day{0}/src/example",
+                day
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            verify,
+        } => run(day, part, input, verify),
+        Command::Bench {
+            day,
+            samples,
+            json,
+            baseline,
+            save_baseline,
+            threshold,
+        } => bench(
+            day,
+            BenchOptions {
+                samples,
+                json,
+                baseline,
+                save_baseline,
+                threshold,
+            },
+        ),
+        Command::NewDay { day } => new_day(day),
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::{DocumentMut, InlineTable, Value};
+
+/// Creates `day<N>` from `_template` and registers it in the workspace and the runner.
+/// Returns the files that were written.
+pub fn new_day(workspace: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let name = format!("day{}", day);
+    let dir = workspace.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let manifest_path = workspace.join("Cargo.toml");
+    let runner_manifest_path = workspace.join("aoc/Cargo.toml");
+    let days_path = workspace.join("aoc/src/days.rs");
+    let manifest = add_member(&read(&manifest_path)?, &name)?;
+    let runner_manifest = add_dependency(&read(&runner_manifest_path)?, &name)?;
+    let days = add_day(&read(&days_path)?, day)?;
+
+    let mut written = vec![];
+    copy_template(&workspace.join("_template"), &dir, day, &mut written)
+        .map_err(|e| format!("failed to copy the template: {}", e))?;
+    for file in ["inputs", "example"] {
+        let path = dir.join("src").join(file);
+        write(&path, "")?;
+        written.push(path);
+    }
+    write(&manifest_path, &manifest)?;
+    write(&runner_manifest_path, &runner_manifest)?;
+    write(&days_path, &days)?;
+    written.extend([manifest_path, runner_manifest_path, days_path]);
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+fn fill_in(template: &str, day: u8) -> String {
+    template
+        .replace("TEMPLATE_NAME", &format!("day{}", day))
+        .replace("TEMPLATE_STRUCT", &format!("Day{}", day))
+        .replace("TEMPLATE_DAY", &day.to_string())
+}
+
+fn copy_template(from: &Path, to: &Path, day: u8, written: &mut Vec<PathBuf>) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let to = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_template(&entry.path(), &to, day, written)?;
+        } else {
+            fs::write(&to, fill_in(&fs::read_to_string(entry.path())?, day))?;
+            written.push(to);
+        }
+    }
+    Ok(())
+}
+
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+    let mut doc: DocumentMut = manifest.parse().map_err(|e| format!("{}", e))?;
+    let members = doc["workspace"]["members"]
+        .as_array_mut()
+        .ok_or("the workspace has no `members` list")?;
+    if members.iter().any(|m| m.as_str() == Some(name)) {
+        return Err(format!("{} is already a workspace member", name));
+    }
+    let decor = members.get(0).map(|m| m.decor().clone());
+    members.push(name);
+    if let (Some(decor), Some(last)) = (decor, members.get_mut(members.len() - 1)) {
+        *last.decor_mut() = decor;
+    }
+    Ok(doc.to_string())
+}
+
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    let mut doc: DocumentMut = manifest.parse().map_err(|e| format!("{}", e))?;
+    let dependencies = doc["dependencies"]
+        .as_table_mut()
+        .ok_or("the runner has no `[dependencies]`")?;
+    let mut path = InlineTable::new();
+    path.insert("path", Value::from(format!("../{}", name)));
+    dependencies.insert(name, toml_edit::value(path));
+    Ok(doc.to_string())
+}
+
+fn add_day(days: &str, day: u8) -> Result<String, String> {
+    let start = days
+        .find("pub const DAYS")
+        .ok_or("the runner has no `DAYS` list")?;
+    let end = start
+        + days[start..]
+            .find("\n];")
+            .ok_or("the `DAYS` list is not closed")?;
+    let (head, tail) = days.split_at(end);
+    Ok(format!(
+        "{head}\n    Day::of::<day{day}::Day{day}>(),{tail}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn _fill_in() {
+        assert_eq!(
+            "use day15::Day15; // 15",
+            fill_in("use TEMPLATE_NAME::TEMPLATE_STRUCT; // TEMPLATE_DAY", 15)
+        );
+    }
+
+    #[test]
+    fn _add_member() {
+        let manifest = "[workspace]\nmembers = [\n  \"common\",\n  \"day1\",\n]\n";
+        assert_eq!(
+            "[workspace]\nmembers = [\n  \"common\",\n  \"day1\",\n  \"day2\",\n]\n",
+            add_member(manifest, "day2").unwrap()
+        );
+        assert!(add_member(manifest, "day1").is_err());
+    }
+
+    #[test]
+    fn _add_dependency() {
+        let manifest = "[dependencies]\nday1 = { path = \"../day1\" }\n";
+        assert_eq!(
+            "[dependencies]\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n",
+            add_dependency(manifest, "day2").unwrap()
+        );
+    }
+
+    #[test]
+    fn _add_day() {
+        let days = "pub const DAYS: &[Day] = &[\n    Day::of::<day1::Day1>(),\n];\n";
+        assert_eq!(
+            "pub const DAYS: &[Day] = &[\n    Day::of::<day1::Day1>(),\n    Day::of::<day2::Day2>(),\n];\n",
+            add_day(days, 2).unwrap()
+        );
+    }
+}
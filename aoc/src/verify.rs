@@ -0,0 +1,71 @@
+use std::{fs, io, path::Path};
+
+use serde::Deserialize;
+
+/// The expected answers recorded in a day's `src/answers.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    /// Reads the answers of the day crate at `dir`. A day without an answers file has no
+    /// recorded answers.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        match fs::read_to_string(dir.join("src").join("answers.toml")) {
+            Ok(s) => toml::from_str(&s).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict<'a> {
+    Correct,
+    Wrong { expected: &'a str },
+    Unrecorded,
+}
+
+/// Compares an answer against the recorded one. Trailing whitespace is ignored so that
+/// multi-line answers can be recorded as TOML multi-line strings.
+pub fn check<'a>(expected: Option<&'a str>, actual: &str) -> Verdict<'a> {
+    match expected {
+        None => Verdict::Unrecorded,
+        Some(expected) if expected.trim_end() == actual.trim_end() => Verdict::Correct,
+        Some(expected) => Verdict::Wrong { expected },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn _multi_line_answer() {
+        let answers: Answers =
+            toml::from_str("part1 = \"17\"\npart2 = \"\"\"\n#.#\n.#.\n\"\"\"\n").unwrap();
+        assert_eq!(Verdict::Correct, check(answers.expected(1), "17"));
+        assert_eq!(Verdict::Correct, check(answers.expected(2), "#.#\n.#.\n"));
+        assert_eq!(
+            Verdict::Wrong {
+                expected: "#.#\n.#.\n"
+            },
+            check(answers.expected(2), "#.#\n...\n")
+        );
+        assert_eq!(
+            Verdict::Unrecorded,
+            check(Answers::default().expected(1), "17")
+        );
+    }
+}
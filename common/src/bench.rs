@@ -0,0 +1,25 @@
+//! Criterion benches shared by every day.
+//!
+//! Each day registers `_parse`, `_answer_part1` and `_answer_part2` under a group named after
+//! the day, so `cargo bench -p day5` measures one day and `cargo bench --workspace` all of them.
+//! Criterion keeps its results under `target/criterion`; pass `-- --save-baseline <name>` to
+//! record a run and `-- --baseline <name>` to compare a later run against it.
+
+use crate::Solution;
+use criterion::{black_box, Criterion};
+use std::path::Path;
+
+pub fn solution<S: Solution>(
+    c: &mut Criterion,
+    manifest_dir: impl AsRef<Path>,
+    embedded: Option<&'static str>,
+) {
+    let input = crate::input::from_crate(manifest_dir, embedded).expect("failed to read input");
+    let parsed = S::parse(&input).expect("failed to parse input");
+
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+    group.bench_function("_parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("_answer_part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("_answer_part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
@@ -0,0 +1,252 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{lines, ParseError};
+
+/// An `(x, y)` position, with `(0, 0)` in the top left corner.
+pub type Point = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses one cell per character, with every row as wide as the first.
+    /// `cell` returns `None` for characters that aren't `expected`.
+    pub fn parse_chars(
+        s: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for l in lines(s) {
+            let start = cells.len();
+            for (i, c) in l.text.char_indices() {
+                if width.is_some_and(|w| cells.len() - start == w) {
+                    return Err(l.error_at(&l.text[i..], "end of the row"));
+                }
+                cells.push(cell(c).ok_or_else(|| l.error_at(&l.text[i..], expected))?);
+            }
+            let row = cells.len() - start;
+            match width {
+                None => width = Some(row),
+                Some(w) if row < w => return Err(l.error_at_end(expected)),
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The up to 4 positions above, right of, below and left of `p`.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> {
+        self.offsets(p, &ORTHOGONAL)
+    }
+
+    /// The up to 8 positions around `p`, diagonals included.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> {
+        self.offsets(p, &ALL_AROUND)
+    }
+
+    fn offsets(
+        &self,
+        (x, y): Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx).filter(|&x| x < width)?;
+            let y = y.checked_add_signed(dy).filter(|&y| y < height)?;
+            Some((x, y))
+        })
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a map of single digits, like `2199943210`.
+    pub fn parse_digits(s: &str) -> Result<Self, ParseError> {
+        Self::parse_chars(s, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).expect("point is inside the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p).expect("point is inside the grid")
+    }
+}
+
+/// Renders each row on its own line, with every cell's `Display` output side by side.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn _neighbours() {
+        let grid = Grid::new(3, 2, ());
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)],
+            grid.neighbours8((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(None, grid.get((3, 0)));
+    }
+
+    #[test]
+    fn _parse_digits() {
+        let grid = Grid::parse_digits("219\n398\n").unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(8, grid[(2, 1)]);
+        assert_eq!("219\n398\n", grid.to_string());
+    }
+
+    #[test]
+    fn _parse_errors() {
+        assert_eq!(
+            Err(ParseError::new(2, 2, "a digit")),
+            Grid::parse_digits("219\n3a8")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 3, "a digit")),
+            Grid::parse_digits("219\n39")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 4, "end of the row")),
+            Grid::parse_digits("219\n3980")
+        );
+    }
+
+    #[test]
+    fn _parse_chars() {
+        let grid = Grid::parse_chars("#.\n.#", "`#` or `.`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(2, grid.iter().filter(|&&b| b).count());
+        assert_eq!(
+            "ab\ncd\n",
+            Grid::from_fn(2, 2, |(x, y)| ["ab", "cd"][y].as_bytes()[x] as char).to_string()
+        );
+    }
+}
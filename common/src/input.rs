@@ -0,0 +1,72 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// Reads the puzzle input of a day binary.
+///
+/// The first command line argument that isn't a `--flag` is the path of the input file, `-`
+/// reads from stdin. Without one the embedded input is used when the crate was built with the
+/// `embedded-input` feature, and stdin otherwise.
+pub fn from_args(embedded: Option<&'static str>) -> io::Result<String> {
+    let mut s = String::new();
+    reader_from_args(embedded)?.read_to_string(&mut s)?;
+    Ok(s)
+}
+
+/// The puzzle input [`from_args`] would read, for binaries that read it a line at a time.
+pub fn reader_from_args(embedded: Option<&'static str>) -> io::Result<Box<dyn BufRead>> {
+    let path = env::args_os()
+        .skip(1)
+        .find(|arg| !arg.to_string_lossy().starts_with("--"));
+    Ok(match (path, embedded) {
+        (Some(path), _) if path != "-" => Box::new(BufReader::new(File::open(path)?)),
+        (None, Some(s)) => Box::new(s.as_bytes()),
+        _ => Box::new(io::stdin().lock()),
+    })
+}
+
+/// The value of `--name=value` on the command line, or an empty string for a bare `--name`.
+pub fn flag(name: &str) -> Option<String> {
+    env::args_os().skip(1).find_map(|arg| {
+        let arg = arg.into_string().ok()?;
+        let rest = arg.strip_prefix("--")?.strip_prefix(name)?;
+        match rest.strip_prefix('=') {
+            Some(value) => Some(value.to_string()),
+            None if rest.is_empty() => Some(String::new()),
+            None => None,
+        }
+    })
+}
+
+/// Reads the puzzle input checked in next to a day's sources, `src/inputs` of the crate at
+/// `manifest_dir`, unless the crate was built with the input embedded.
+///
+/// This is where benches get their input from, pass it `env!("CARGO_MANIFEST_DIR")`.
+pub fn from_crate(
+    manifest_dir: impl AsRef<Path>,
+    embedded: Option<&'static str>,
+) -> io::Result<String> {
+    match embedded {
+        Some(s) => Ok(s.to_string()),
+        None => fs::read_to_string(manifest_dir.as_ref().join("src").join("inputs")),
+    }
+}
+
+/// Reads the file at `path`, or stdin if `path` is `-`.
+pub fn from_path(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    if path == Path::new("-") {
+        from_stdin()
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+pub fn from_stdin() -> io::Result<String> {
+    let mut s = String::new();
+    io::stdin().read_to_string(&mut s)?;
+    Ok(s)
+}
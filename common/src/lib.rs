@@ -0,0 +1,23 @@
+use std::fmt::Display;
+
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod grid;
+pub mod input;
+pub mod parse;
+
+pub use grid::Grid;
+pub use parse::ParseError;
+
+/// The shape every day's puzzle shares: parse the input once, then answer both parts from it.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
@@ -0,0 +1,205 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Where and why a puzzle input failed to parse. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at byte `offset` of `s`, for parsers that don't work line by line.
+    pub fn at_offset(s: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let before = &s[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        Self::new(line, column, expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of puzzle input and its line number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at the start of `part`, which must be a slice of this line.
+    pub fn error_at(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|&offset| offset <= self.text.len())
+            .expect("part is a slice of the line");
+        let column = self.text[..offset].chars().count() + 1;
+        ParseError::new(self.number, column, expected)
+    }
+
+    /// An error pointing just past the end of the line, for when something is missing.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(&self.text[self.text.len()..], expected)
+    }
+
+    /// Parses `part`, a slice of this line, reporting `expected` if it isn't a valid `T`.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error_at(part, expected))
+    }
+
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error_at_end(format!("`{}`", delimiter)))
+    }
+
+    /// Splits the line on `delimiter` and parses every piece.
+    pub fn parse_list<T: FromStr>(
+        &self,
+        delimiter: char,
+        expected: &str,
+    ) -> Result<Vec<T>, ParseError> {
+        self.text
+            .split(delimiter)
+            .map(|part| self.parse(part, expected))
+            .collect()
+    }
+}
+
+/// Every line of `s`, blank ones included, without line endings or trailing whitespace.
+fn all_lines(s: &str) -> impl Iterator<Item = Line<'_>> {
+    s.split('\n').enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text: text.trim_end(),
+    })
+}
+
+/// The non-blank lines of `s`, numbered as they appear in `s`.
+///
+/// Both `\n` and `\r\n` line endings are accepted and trailing whitespace is stripped, so
+/// a trailing newline or a file saved on Windows parses the same as the original input.
+pub fn lines(s: &str) -> impl Iterator<Item = Line<'_>> {
+    all_lines(s).filter(|l| !l.text.is_empty())
+}
+
+/// The non-blank lines of `s` grouped into sections separated by one or more blank lines.
+pub fn sections(s: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![];
+    let mut section = vec![];
+    for l in all_lines(s) {
+        if !l.text.is_empty() {
+            section.push(l);
+        } else if !section.is_empty() {
+            sections.push(section);
+            section = vec![];
+        }
+    }
+    if !section.is_empty() {
+        sections.push(section);
+    }
+    sections
+}
+
+/// Splits `s` into exactly `N` sections, reporting `expected[i]` if section `i` is missing.
+pub fn split_sections<'a, const N: usize>(
+    s: &'a str,
+    expected: [&str; N],
+) -> Result<[Vec<Line<'a>>; N], ParseError> {
+    let mut sections = sections(s).into_iter();
+    let mut out: [Vec<Line>; N] = std::array::from_fn(|_| vec![]);
+    for (section, expected) in out.iter_mut().zip(expected) {
+        *section = sections
+            .next()
+            .ok_or_else(|| ParseError::at_offset(s, s.len(), expected))?;
+    }
+    if let Some(extra) = sections.next() {
+        return Err(extra[0].error_at(extra[0].text, "end of input"));
+    }
+    Ok(out)
+}
+
+/// `s` with `\n` line endings, no trailing whitespace on any line and no blank lines at the
+/// end, for parsers that work on the whole input rather than line by line. Line and column
+/// numbers in the result are the same as in `s`.
+pub fn normalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for l in all_lines(s) {
+        out.push_str(l.text);
+        out.push('\n');
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn _at_offset() {
+        let s = "ab\ncde\nf";
+        assert_eq!(ParseError::new(1, 1, "x"), ParseError::at_offset(s, 0, "x"));
+        assert_eq!(ParseError::new(2, 3, "x"), ParseError::at_offset(s, 5, "x"));
+        assert_eq!(ParseError::new(3, 2, "x"), ParseError::at_offset(s, 8, "x"));
+    }
+
+    #[test]
+    fn _line_errors() {
+        let line = lines("1,2\n3,x").nth(1).unwrap();
+        assert_eq!(
+            Err(ParseError::new(2, 3, "a number")),
+            line.parse_list::<u32>(',', "a number")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 4, "` -> `")),
+            line.split_once(" -> ")
+        );
+        assert_eq!(
+            "line 2, column 4: expected ` -> `",
+            line.split_once(" -> ").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let s = "1 \r\n2\r\n\r\n \r\n3\n\n";
+        let numbered: Vec<_> = lines(s).map(|l| (l.number, l.text)).collect();
+        assert_eq!(vec![(1, "1"), (2, "2"), (5, "3")], numbered);
+
+        let sections: Vec<Vec<_>> = sections(s)
+            .iter()
+            .map(|s| s.iter().map(|l| l.text).collect())
+            .collect();
+        assert_eq!(vec![vec!["1", "2"], vec!["3"]], sections);
+
+        assert_eq!("1\n2\n\n\n3", normalize(s));
+
+        assert_eq!(
+            Err(ParseError::new(7, 1, "more")),
+            split_sections(s, ["numbers", "more", "more"]).map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(5, 1, "end of input")),
+            split_sections(s, ["numbers"]).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use common::{
+    parse::{lines, Line},
+    ParseError, Solution,
+};
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    io::{self, BufRead},
+};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub fn parse(s: &str) -> Result<Vec<u32>, ParseError> {
+    lines(s).map(|l| l.parse(l.text, "a number")).collect()
+}
+
+/// A full window of readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Window {
+    pub sum: u64,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+
+/// Slides a window over the readings one at a time, keeping only the window in memory.
+pub struct Sweep<I> {
+    readings: I,
+    size: usize,
+    window: VecDeque<u32>,
+    sum: u64,
+    // Candidates for the minimum and maximum, oldest first, as (index, reading).
+    mins: VecDeque<(usize, u32)>,
+    maxs: VecDeque<(usize, u32)>,
+    index: usize,
+}
+
+impl<I: Iterator<Item = u32>> Iterator for Sweep<I> {
+    type Item = Window;
+
+    fn next(&mut self) -> Option<Window> {
+        if self.size == 0 {
+            return None;
+        }
+        loop {
+            let reading = self.readings.next()?;
+            let index = self.index;
+            self.index += 1;
+
+            self.window.push_back(reading);
+            self.sum += reading as u64;
+            if self.window.len() > self.size {
+                self.sum -= self.window.pop_front().unwrap() as u64;
+            }
+            while self.mins.back().is_some_and(|&(_, m)| m >= reading) {
+                self.mins.pop_back();
+            }
+            self.mins.push_back((index, reading));
+            while self.maxs.back().is_some_and(|&(_, m)| m <= reading) {
+                self.maxs.pop_back();
+            }
+            self.maxs.push_back((index, reading));
+            let oldest = (index + 1).saturating_sub(self.size);
+            while self.mins.front().is_some_and(|&(i, _)| i < oldest) {
+                self.mins.pop_front();
+            }
+            while self.maxs.front().is_some_and(|&(i, _)| i < oldest) {
+                self.maxs.pop_front();
+            }
+
+            if self.window.len() == self.size {
+                return Some(Window {
+                    sum: self.sum,
+                    min: self.mins[0].1,
+                    max: self.maxs[0].1,
+                    mean: self.sum as f64 / self.size as f64,
+                });
+            }
+        }
+    }
+}
+
+/// Every window of `size` consecutive readings. Fewer readings than that give no windows.
+pub fn sweep<I: IntoIterator<Item = u32>>(readings: I, size: usize) -> Sweep<I::IntoIter> {
+    Sweep {
+        readings: readings.into_iter(),
+        size,
+        // Only a hint, a huge window grows as readings arrive rather than allocating up front.
+        window: VecDeque::with_capacity(size.saturating_add(1).min(1 << 12)),
+        sum: 0,
+        mins: VecDeque::new(),
+        maxs: VecDeque::new(),
+        index: 0,
+    }
+}
+
+/// How often the sum of a window went up, down or stayed the same compared to the one before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub increases: u32,
+    pub decreases: u32,
+    pub plateaus: u32,
+}
+
+impl Changes {
+    fn count(&mut self, prev: u64, sum: u64) {
+        match sum.cmp(&prev) {
+            Ordering::Greater => self.increases += 1,
+            Ordering::Less => self.decreases += 1,
+            Ordering::Equal => self.plateaus += 1,
+        }
+    }
+}
+
+/// Counts the changes of one window size as the readings arrive.
+struct Counter {
+    size: usize,
+    window: VecDeque<u32>,
+    sum: u64,
+    prev: Option<u64>,
+    changes: Changes,
+}
+
+impl Counter {
+    fn new(size: usize) -> Self {
+        Counter {
+            size,
+            window: VecDeque::with_capacity(size.saturating_add(1).min(1 << 12)),
+            sum: 0,
+            prev: None,
+            changes: Changes::default(),
+        }
+    }
+
+    fn push(&mut self, reading: u32) {
+        if self.size == 0 {
+            return;
+        }
+        self.window.push_back(reading);
+        self.sum += reading as u64;
+        if self.window.len() > self.size {
+            self.sum -= self.window.pop_front().unwrap() as u64;
+        }
+        if self.window.len() == self.size {
+            if let Some(prev) = self.prev {
+                self.changes.count(prev, self.sum);
+            }
+            self.prev = Some(self.sum);
+        }
+    }
+}
+
+pub fn changes(readings: impl IntoIterator<Item = u32>, window_size: usize) -> Changes {
+    let mut counter = Counter::new(window_size);
+    readings.into_iter().for_each(|r| counter.push(r));
+    counter.changes
+}
+
+/// Reads one reading per line without loading the whole input, skipping blank lines.
+pub fn read(input: impl BufRead) -> impl Iterator<Item = io::Result<u32>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| l.as_ref().map_or(true, |l| !l.trim().is_empty()))
+        .map(|(i, l)| {
+            let text = l?;
+            let l = Line {
+                number: i + 1,
+                text: text.trim_end(),
+            };
+            l.parse(l.text, "a number")
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+}
+
+/// The changes for each of the window sizes, reading `readings` once and keeping only the
+/// windows in memory. Stops at the first error.
+pub fn stream_changes<const N: usize>(
+    mut readings: impl Iterator<Item = io::Result<u32>>,
+    window_sizes: [usize; N],
+) -> io::Result<[Changes; N]> {
+    let mut counters = window_sizes.map(Counter::new);
+    readings.try_for_each(|reading| -> io::Result<()> {
+        let reading = reading?;
+        counters.iter_mut().for_each(|c| c.push(reading));
+        Ok(())
+    })?;
+    Ok(counters.map(|c| c.changes))
+}
+
+pub fn count_increasing(window_size: usize, meas: &[u32]) -> u32 {
+    changes(meas.iter().copied(), window_size).increases
+}
+
+pub fn answer_part1(meas: &[u32]) -> u32 {
+    count_increasing(1, meas)
+}
+
+pub fn answer_part2(meas: &[u32]) -> u32 {
+    count_increasing(3, meas)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_DATA: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+    #[test]
+    fn test() {
+        let data = parse(EXAMPLE_DATA).unwrap();
+        assert_eq!(count_increasing(1, &data), 7);
+        assert_eq!(count_increasing(3, &data), 5);
+    }
+
+    #[test]
+    fn _short_input() {
+        assert_eq!(0, count_increasing(3, &[199, 200]));
+        assert_eq!(0, count_increasing(1, &[]));
+        assert_eq!(Changes::default(), changes([1, 2, 3], 0));
+        assert_eq!(None, sweep([1, 2], 3).next());
+        assert_eq!(None, sweep([1, 2], usize::MAX).next());
+    }
+
+    #[test]
+    fn _changes() {
+        let data = parse(EXAMPLE_DATA).unwrap();
+        assert_eq!(
+            Changes {
+                increases: 7,
+                decreases: 2,
+                plateaus: 0
+            },
+            changes(data.iter().copied(), 1)
+        );
+        assert_eq!(
+            Changes {
+                increases: 5,
+                decreases: 1,
+                plateaus: 1
+            },
+            changes(data.iter().copied(), 3)
+        );
+    }
+
+    #[test]
+    fn _sweep() {
+        let data = parse(EXAMPLE_DATA).unwrap();
+        let windows: Vec<_> = sweep(data.iter().copied(), 3).collect();
+        assert_eq!(8, windows.len());
+        for (window, readings) in windows.iter().zip(data.windows(3)) {
+            assert_eq!(readings.iter().map(|&r| r as u64).sum::<u64>(), window.sum);
+            assert_eq!(*readings.iter().min().unwrap(), window.min);
+            assert_eq!(*readings.iter().max().unwrap(), window.max);
+        }
+        assert_eq!(
+            Window {
+                sum: 607,
+                min: 199,
+                max: 208,
+                mean: 607.0 / 3.0
+            },
+            windows[0]
+        );
+    }
+
+    #[test]
+    fn _read() {
+        let input = EXAMPLE_DATA.replace('\n', " \r\n") + "\r\n\r\n";
+        let readings = read(input.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(parse(EXAMPLE_DATA).unwrap(), readings);
+
+        let e = read("199\n\n2x0".as_bytes()).nth(1).unwrap().unwrap_err();
+        assert_eq!("line 3, column 1: expected a number", e.to_string());
+    }
+
+    #[test]
+    fn _stream_changes() {
+        let data = parse(EXAMPLE_DATA).unwrap();
+        assert_eq!(
+            [
+                changes(data.iter().copied(), 1),
+                changes(data.iter().copied(), 3)
+            ],
+            stream_changes(read(EXAMPLE_DATA.as_bytes()), [1, 3]).unwrap()
+        );
+        assert_eq!(
+            [Changes::default()],
+            stream_changes(read("".as_bytes()), [usize::MAX]).unwrap()
+        );
+        let e = stream_changes(read("199\n2x0".as_bytes()), [1, 3]).unwrap_err();
+        assert_eq!("line 2, column 1: expected a number", e.to_string());
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 1, "a number")),
+            parse("199\n2x0").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_DATA.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(7, answer_part1(&parse(&input).unwrap()));
+    }
+}
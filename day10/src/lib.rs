@@ -0,0 +1,135 @@
+use common::{parse::lines, ParseError, Solution};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub fn parse(s: &str) -> Result<Vec<String>, ParseError> {
+    lines(s)
+        .map(|l| match l.text.find(|c| !"()[]{}<>".contains(c)) {
+            Some(i) => Err(l.error_at(&l.text[i..], "a bracket")),
+            None => Ok(l.text.to_string()),
+        })
+        .collect()
+}
+
+pub fn parse_line(s: &str) -> (usize, Vec<char>) {
+    let mut stack: Vec<char> = vec![];
+    for c in s.chars() {
+        match c {
+            ')' => if stack.last() == Some(&'(') {
+                stack.pop();
+            } else {
+                return (3, stack);
+            }
+            ']' => if stack.last() == Some(&'[') {
+                stack.pop();
+            } else {
+                return (57, stack);
+            }
+            '}' => if stack.last() == Some(&'{') {
+                stack.pop();
+            } else {
+                return (1197, stack);
+            }
+            '>' => if stack.last() == Some(&'<') {
+                stack.pop();
+            } else {
+                return (25137, stack);
+            }
+            c => {
+                stack.push(c);
+            }
+        }
+    }
+    (0, stack)
+}
+
+pub fn answer_part1(g: &[String]) -> usize {
+    g.iter().map(|s| parse_line(s.as_str()).0).sum()
+}
+
+pub fn answer_part2(g: &[String]) -> usize {
+    let mut scores: Vec<_> = g
+        .iter()
+        .filter_map(|s| match parse_line(s.as_str()) {
+            (0, stack) => Some(stack),
+            _ => None,
+        })
+        .map(|stack| {
+            stack.iter().rev().fold(0, |sum, c| sum * 5 + match c {
+                '(' => 1,
+                '[' => 2,
+                '{' => 3,
+                '<' => 4,
+                _ => panic!("unexpected char: {}", c)
+            })
+        })
+        .collect();
+    scores.sort();
+    scores[scores.len() / 2]
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn test_part1() {
+        let g = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(26397, answer_part1(&g));
+    }
+    #[test]
+    fn test_part2() {
+        let g = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(288957, answer_part2(&g));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 2, "a bracket")),
+            parse("[(\n{x}").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(26397, answer_part1(&parse(&input).unwrap()));
+    }
+}
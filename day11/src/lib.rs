@@ -0,0 +1,107 @@
+use common::{grid::Point, ParseError, Solution};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Grid = common::Grid<u8>;
+
+pub fn parse(s: &str) -> Result<Grid, ParseError> {
+    Grid::parse_digits(s)
+}
+
+pub fn step(g: &mut Grid) -> usize {
+    let mut to_visit: Vec<Point> = g.points().collect();
+    while let Some(p) = to_visit.pop() {
+        let n = g[p];
+        if n == 9 {
+            to_visit.extend(g.neighbours8(p));
+        }
+        g[p] = n + 1;
+    }
+    for cell in g.iter_mut() {
+        if *cell > 9 {
+            *cell = 0;
+        }
+    }
+    g.iter().filter(|&&c| c == 0).count()
+}
+
+pub fn answer_part1(g: &Grid) -> usize {
+    let mut g = g.clone();
+    (0..100).map(|_| step(&mut g)).sum()
+}
+
+pub fn answer_part2(g: &Grid) -> usize {
+    let mut g = g.clone();
+    let cell_count = g.width() * g.height();
+    (0..)
+        .map(|i| (i + 1, step(&mut g)))
+        .find(|(_, flashes)| *flashes == cell_count)
+        .unwrap()
+        .0
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Grid;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn test_part1() {
+        let g = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(1656, answer_part1(&g));
+    }
+    #[test]
+    fn test_part2() {
+        let g = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(195, answer_part2(&g));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 2, "a digit")),
+            parse("219\n3a8").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(1656, answer_part1(&parse(&input).unwrap()));
+    }
+}
@@ -0,0 +1,9 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day12::{Day12, EMBEDDED_INPUT};
+
+fn solution(c: &mut Criterion) {
+    common::bench::solution::<Day12>(c, env!("CARGO_MANIFEST_DIR"), EMBEDDED_INPUT);
+}
+
+criterion_group!(benches, solution);
+criterion_main!(benches);
@@ -0,0 +1,228 @@
+use common::{parse::lines, ParseError, Solution};
+use multimap::MultiMap;
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Input = Vec<(String, String)>;
+pub type Path = (bool, Vec<String>);
+
+pub fn parse(s: &str) -> Result<Input, ParseError> {
+    lines(s)
+        .map(|l| {
+            let (a, b) = l.split_once("-")?;
+            if a.is_empty() {
+                return Err(l.error_at(a, "a cave name"));
+            }
+            if b.is_empty() {
+                return Err(l.error_at(b, "a cave name"));
+            }
+            Ok((a.to_string(), b.to_string()))
+        })
+        .collect()
+}
+
+pub struct PathFinder {
+    graph: MultiMap<String, String>,
+    queue: Vec::<Path>,
+}
+
+impl PathFinder {
+    fn new(inputs: &Input) -> Self {
+        let mut graph = MultiMap::new();
+        for (a, b) in inputs.iter()
+        {
+            let a = a.to_string();
+            let b = b.to_string();
+            if a == "start" || b == "end" {
+                graph.insert(a, b);
+            } else if b == "start" || a == "end" {
+                graph.insert(b, a);
+            } else {
+                graph.insert(a.clone(), b.clone());
+                graph.insert(b, a);
+            }
+        }
+        Self {
+            graph,
+            queue: vec![(false, vec!["start".to_string()])],
+        }
+    }
+
+    fn next_part1(&mut self) -> Option<Path> {
+        let mut result: Option<Path> = None;
+        loop {
+            if result.is_some() {
+                break;
+            }
+            let path = match self.queue.pop() {
+                None => break,
+                Some(p)=> p,
+            };
+            for next in self.graph
+                .get_vec(path.1.last().unwrap())
+                .unwrap()
+                .iter()
+                .filter(|&a| Self::is_upper(a) || !path.1.contains(a))
+            {
+                let mut next_path: Path = path.clone();
+                next_path.1.push(next.to_string());
+                if next == "end" && result.is_none() {
+                    result = Some(next_path);
+                } else {
+                    self.queue.push(next_path);
+                }
+            }
+        }
+        result
+    }
+
+    fn next_part2(&mut self) -> Option<Path> {
+        let mut result: Option<Path> = None;
+        loop {
+            if result.is_some() {
+                break;
+            }
+            let path = match self.queue.pop() {
+                None => break,
+                Some(p)=> p,
+            };
+            for next in self.graph
+                .get_vec(path.1.last().unwrap())
+                .unwrap()
+                .iter()
+            {
+                let mut next_path =
+                    if !Self::is_upper(next) && !path.0 && path.1.contains(next) {
+                        let mut p = path.clone();
+                        p.0 = true;
+                        p
+                    } else if Self::is_upper(next) || !path.1.contains(next) {
+                        path.clone()
+                    } else {
+                        continue;
+                    };
+                next_path.1.push(next.to_string());
+                if next == "end" && result.is_none() {
+                    result = Some(next_path);
+                } else {
+                    self.queue.push(next_path);
+                }
+            }
+        }
+        result
+    }
+
+    fn is_upper(s: &str) -> bool {
+        s.chars().nth(0).unwrap().is_uppercase()
+    }
+}
+
+pub fn answer_part1(inputs: &Input) -> usize {
+    let mut pf = PathFinder::new(inputs);
+    let mut count = 0;
+    while let Some(_path) = pf.next_part1() {
+        count += 1;
+    }
+    count
+}
+
+pub fn answer_part2(inputs: &Input) -> usize {
+    let mut pf = PathFinder::new(inputs);
+    let mut count = 0;
+    while let Some(_path) = pf.next_part2() {
+        count += 1;
+    }
+    count
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    const EXAMPLE_INPUT_2: &str = "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+    #[test]
+    fn _part1_a() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(10, answer_part1(&inputs));
+    }
+    #[test]
+    fn _part1_b() {
+        let inputs = parse(EXAMPLE_INPUT_2).unwrap();
+        assert_eq!(226, answer_part1(&inputs));
+    }
+    #[test]
+    fn _part2_a() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(36, answer_part2(&inputs));
+    }
+    #[test]
+    fn _part2_b() {
+        let inputs = parse(EXAMPLE_INPUT_2).unwrap();
+        assert_eq!(3509, answer_part2(&inputs));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 6, "`-`")),
+            parse("start-A\nA end").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(10, answer_part1(&parse(&input).unwrap()));
+    }
+}
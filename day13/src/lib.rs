@@ -0,0 +1,149 @@
+use common::{parse::split_sections, ParseError, Solution};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Fold {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+pub type Grid = common::Grid<bool>;
+pub type Input = (Grid, Vec<Fold>);
+
+pub fn parse(s: &str) -> Result<Input, ParseError> {
+    let [dots, folds] = split_sections(s, ["dot coordinates", "fold instructions"])?;
+    let dots: Vec<(usize, usize)> = dots
+        .iter()
+        .map(|l| {
+            let (x, y) = l.split_once(",")?;
+            Ok((l.parse(x, "a number")?, l.parse(y, "a number")?))
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    let (width, height) = dots
+        .iter()
+        .fold((0, 0), |(xm, ym), &(x, y)| (xm.max(x), ym.max(y)));
+    let mut grid = Grid::new(width + 1, height + 1, false);
+
+    for dot in dots.into_iter() {
+        grid[dot] = true;
+    }
+
+    let folds: Vec<Fold> = folds
+        .iter()
+        .map(|l| {
+            let (axis, n) = l
+                .text
+                .strip_prefix("fold along ")
+                .and_then(|f| f.split_once('='))
+                .ok_or_else(|| l.error_at(l.text, "`fold along x=` or `fold along y=`"))?;
+            let n = l.parse(n, "a number")?;
+            match axis {
+                "y" => Ok(Fold::Vertical(n)),
+                "x" => Ok(Fold::Horizontal(n)),
+                _ => Err(l.error_at(axis, "`x` or `y`")),
+            }
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok((grid, folds))
+}
+
+pub fn step(grid: &mut Grid, fold: Fold) {
+    let (width, height) = (grid.width(), grid.height());
+    *grid = match fold {
+        Fold::Horizontal(split_x) => Grid::from_fn(split_x, height, |(x, y)| {
+            grid[(x, y)] || grid[(width - 1 - x, y)]
+        }),
+        Fold::Vertical(split_y) => Grid::from_fn(width, split_y, |(x, y)| {
+            grid[(x, y)] || grid[(x, height - 1 - y)]
+        }),
+    };
+}
+
+pub fn answer_part1((grid, folds): &Input) -> usize {
+    let mut grid = grid.clone();
+    step(&mut grid, folds[0]);
+    grid.iter().filter(|&&b| b).count()
+}
+
+pub fn answer_part2((grid, folds): &Input) -> String {
+    let mut grid = grid.clone();
+    for fold in folds.iter() {
+        step(&mut grid, *fold);
+    }
+    grid.map(|&b| if b { '#' } else { '.' }).to_string()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Input;
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn _part1() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(17, answer_part1(&inputs));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(3, 12, "`x` or `y`")),
+            parse("6,10\n\nfold along z=3").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(17, answer_part1(&parse(&input).unwrap()));
+    }
+}
@@ -0,0 +1,466 @@
+use common::{parse::split_sections, ParseError, Solution};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fmt, io,
+};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Template = Vec<u8>;
+pub type Rule = (u8, u8, u8);
+
+pub type Input = (Template, Rules);
+
+/// Insertion rules looked up by the pair they apply to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rules(HashMap<(u8, u8), u8>);
+
+impl Rules {
+    /// The element inserted between `a` and `b`, if any.
+    pub fn get(&self, a: u8, b: u8) -> Option<u8> {
+        self.0.get(&(a, b)).copied()
+    }
+}
+
+impl FromIterator<Rule> for Rules {
+    fn from_iter<I: IntoIterator<Item = Rule>>(rules: I) -> Self {
+        Self(rules.into_iter().map(|(a, b, c)| ((a, b), c)).collect())
+    }
+}
+
+/// How often each element occurs in a polymer.
+pub type Histogram = BTreeMap<u8, u64>;
+/// How often each pair of adjacent elements occurs in a polymer.
+pub type PairCounts = HashMap<(u8, u8), u64>;
+
+/// The polymer grew longer than a `u64` can count on `step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub step: usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the element counts overflow on step {}", self.step)
+    }
+}
+
+impl Error for Overflow {}
+
+pub fn parse(s: &str) -> Result<Input, ParseError> {
+    let [template, rules] = split_sections(s, ["a polymer template", "insertion rules"])?;
+    if let [_, l, ..] = template.as_slice() {
+        return Err(l.error_at(l.text, "a blank line after the template"));
+    }
+    let template: Template = template[0].text.bytes().collect();
+
+    let rules: Rules = rules
+        .iter()
+        .map(|l| {
+            let (pair, insert) = l.split_once(" -> ")?;
+            match (pair.as_bytes(), insert.as_bytes()) {
+                (&[a, b], &[c]) => Ok((a, b, c)),
+                (&[_, _], _) => Err(l.error_at(insert, "a single element")),
+                _ => Err(l.error_at(pair, "a pair of elements")),
+            }
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok((template, rules))
+}
+
+pub fn step(template: Template, rules: &Rules) -> Template {
+    let mut output = Template::with_capacity(template.len() * 2);
+    for w in template.windows(2) {
+        output.push(w[0]);
+        output.extend(rules.get(w[0], w[1]));
+    }
+    output.extend(template.last());
+    output
+}
+
+pub fn count_elements(template: &[u8]) -> Histogram {
+    let mut histogram = Histogram::new();
+    for &c in template {
+        *histogram.entry(c).or_default() += 1;
+    }
+    histogram
+}
+
+/// The most common element's count minus the least common one's.
+pub fn spread(histogram: &Histogram) -> u64 {
+    let max = histogram.values().max().unwrap_or(&0);
+    let min = histogram.values().min().unwrap_or(&0);
+    max - min
+}
+
+pub fn answer_part1((template, rules): &Input) -> u64 {
+    let mut template = template.clone();
+    for _ in 0..10 {
+        template = step(template, rules);
+    }
+    spread(&count_elements(&template))
+}
+
+pub fn pair_counts(template: &[u8]) -> PairCounts {
+    let mut pairs = PairCounts::new();
+    for w in template.windows(2) {
+        *pairs.entry((w[0], w[1])).or_default() += 1;
+    }
+    pairs
+}
+
+/// One step on pair counts: a pair `AB` with rule `AB -> C` becomes the pairs `AC` and `CB`.
+/// `None` if the polymer's length, and so some count, no longer fits in a `u64`.
+pub fn step_pairs(pairs: &PairCounts, rules: &Rules) -> Option<PairCounts> {
+    let mut output = PairCounts::new();
+    // One more element than pairs. No count exceeds the length, so it's the only one to check.
+    let mut length = 1u64;
+    let mut add = |pair, n| {
+        length = length.checked_add(n)?;
+        *output.entry(pair).or_default() += n;
+        Some(())
+    };
+    for (&(a, b), &n) in pairs {
+        match rules.get(a, b) {
+            Some(c) => {
+                add((a, c), n)?;
+                add((c, b), n)?;
+            }
+            None => add((a, b), n)?,
+        }
+    }
+    Some(output)
+}
+
+/// The element counts of the polymer grown from `template` for `steps` steps.
+///
+/// The polymer about doubles in length every step, so with every pair matching a rule this
+/// fails after 60 or so steps, when it gets longer than `u64::MAX`.
+pub fn histogram(template: &[u8], rules: &Rules, steps: usize) -> Result<Histogram, Overflow> {
+    let mut pairs = pair_counts(template);
+    for step in 1..=steps {
+        pairs = step_pairs(&pairs, rules).ok_or(Overflow { step })?;
+    }
+    Ok(count_pair_elements(&pairs, template.last().copied()))
+}
+
+fn count_pair_elements(pairs: &PairCounts, last: Option<u8>) -> Histogram {
+    // Every element starts exactly one pair, except the last one, which never changes.
+    let mut histogram = Histogram::new();
+    for (&(a, _), &n) in pairs {
+        *histogram.entry(a).or_default() += n;
+    }
+    if let Some(last) = last {
+        *histogram.entry(last).or_default() += 1;
+    }
+    histogram
+}
+
+/// Polymers longer than this are traced as pair counts rather than element by element.
+pub const LITERAL_LIMIT: usize = 1 << 16;
+
+enum Polymer {
+    Literal(Template),
+    Pairs { pairs: PairCounts, last: Option<u8> },
+}
+
+/// The state of the polymer after one step of a [`Trace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub step: usize,
+    pub length: u64,
+    pub histogram: Histogram,
+    pub spread: u64,
+}
+
+/// Runs the insertion rules for a number of steps, yielding the polymer after each one.
+/// Once the polymer gets too long to count, yields the [`Overflow`] and ends.
+///
+/// The polymer is grown literally while it's short, which keeps the trace exact for rules
+/// under experiment, and switches to pair counts once it's longer than the threshold.
+pub struct Trace<'a> {
+    rules: &'a Rules,
+    polymer: Polymer,
+    step: usize,
+    steps: usize,
+    threshold: usize,
+}
+
+impl<'a> Trace<'a> {
+    pub fn new(template: &[u8], rules: &'a Rules, steps: usize) -> Self {
+        Self {
+            rules,
+            polymer: Polymer::Literal(template.to_vec()),
+            step: 0,
+            steps,
+            threshold: LITERAL_LIMIT,
+        }
+    }
+
+    /// Switch to pair counts once the polymer is longer than `threshold` instead of
+    /// [`LITERAL_LIMIT`].
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Whether the polymer is currently grown element by element.
+    pub fn is_literal(&self) -> bool {
+        matches!(self.polymer, Polymer::Literal(_))
+    }
+
+    /// Every element that can occur in the trace: the template's and the inserted ones.
+    pub fn elements(&self) -> BTreeSet<u8> {
+        let mut elements: BTreeSet<u8> = self.rules.0.values().copied().collect();
+        match &self.polymer {
+            Polymer::Literal(template) => elements.extend(template),
+            Polymer::Pairs { pairs, last } => {
+                elements.extend(pairs.keys().flat_map(|&(a, b)| [a, b]));
+                elements.extend(last);
+            }
+        }
+        elements
+    }
+
+    /// Writes the remaining steps as CSV: the step, the polymer's length, its spread and a
+    /// count column for each of the [`elements`](Self::elements). An overflow fails with
+    /// [`io::ErrorKind::InvalidData`] after the rows before it.
+    pub fn write_csv(self, mut out: impl io::Write) -> io::Result<()> {
+        let elements = self.elements();
+        write!(out, "step,length,spread")?;
+        for &e in &elements {
+            write!(out, ",{}", e as char)?;
+        }
+        writeln!(out)?;
+        for step in self {
+            let step = step.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            write!(out, "{},{},{}", step.step, step.length, step.spread)?;
+            for e in &elements {
+                write!(out, ",{}", step.histogram.get(e).unwrap_or(&0))?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for Trace<'_> {
+    type Item = Result<TraceStep, Overflow>;
+
+    fn next(&mut self) -> Option<Result<TraceStep, Overflow>> {
+        if self.step == self.steps {
+            return None;
+        }
+        self.step += 1;
+        let overflow = Overflow { step: self.step };
+
+        let histogram = match &mut self.polymer {
+            Polymer::Literal(template) => {
+                *template = step(std::mem::take(template), self.rules);
+                count_elements(template)
+            }
+            Polymer::Pairs { pairs, last } => {
+                let Some(next) = step_pairs(pairs, self.rules) else {
+                    self.steps = self.step;
+                    return Some(Err(overflow));
+                };
+                *pairs = next;
+                count_pair_elements(pairs, *last)
+            }
+        };
+        if let Polymer::Literal(template) = &self.polymer {
+            if template.len() > self.threshold {
+                self.polymer = Polymer::Pairs {
+                    pairs: pair_counts(template),
+                    last: template.last().copied(),
+                };
+            }
+        }
+        Some(Ok(TraceStep {
+            step: self.step,
+            length: histogram.values().sum(),
+            spread: spread(&histogram),
+            histogram,
+        }))
+    }
+}
+
+pub fn answer_part2((template, rules): &Input) -> u64 {
+    spread(&histogram(template, rules, 40).unwrap_or_else(|e| panic!("{}", e)))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Input;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn _step() {
+        let (template, rules) = parse(EXAMPLE_INPUT).unwrap();
+        let template1 = step(template.clone(), &rules);
+        let template2 = step(template1.clone(), &rules);
+        let template3 = step(template2.clone(), &rules);
+        let template4 = step(template3.clone(), &rules);
+        assert_eq!("NNCB".as_bytes(), template);
+        assert_eq!("NCNBCHB".as_bytes(), template1);
+        assert_eq!("NBCCNBBBCBHCB".as_bytes(), template2);
+        assert_eq!("NBBBCNCCNBBNBNBBCHBHHBCHB".as_bytes(), template3);
+        assert_eq!(
+            "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB".as_bytes(),
+            template4
+        );
+    }
+    #[test]
+    fn _part1() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(1_588, answer_part1(&inputs));
+    }
+    #[test]
+    fn _part2() {
+        let inputs = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(2_188_189_693_529, answer_part2(&inputs));
+    }
+
+    #[test]
+    fn _histogram() {
+        let (template, rules) = parse(EXAMPLE_INPUT).unwrap();
+        let histogram = histogram(&template, &rules, 10).unwrap();
+        assert_eq!(
+            count_elements(&(0..10).fold(template, |t, _| step(t, &rules))),
+            histogram
+        );
+        assert_eq!(3073, histogram.values().sum::<u64>());
+        assert_eq!(Some(&1749), histogram.get(&b'B'));
+        assert_eq!(Some(&298), histogram.get(&b'C'));
+        assert_eq!(Some(&161), histogram.get(&b'H'));
+        assert_eq!(Some(&865), histogram.get(&b'N'));
+    }
+
+    #[test]
+    fn _histogram_overflow() {
+        let (template, rules) = parse(EXAMPLE_INPUT).unwrap();
+        // 3 pairs, each becoming 2 every step, fit for 62 steps.
+        let length = histogram(&template, &rules, 62)
+            .unwrap()
+            .values()
+            .sum::<u64>();
+        assert_eq!(3 << 62, length - 1);
+        assert_eq!(Err(Overflow { step: 63 }), histogram(&template, &rules, 80));
+        assert_eq!(
+            "the element counts overflow on step 63",
+            Overflow { step: 63 }.to_string()
+        );
+    }
+
+    #[test]
+    fn _trace() {
+        let (template, rules) = parse(EXAMPLE_INPUT).unwrap();
+        let literal: Vec<_> = Trace::new(&template, &rules, 10)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let mut trace = Trace::new(&template, &rules, 10).with_threshold(20);
+        let mut switched = vec![];
+        for _ in 0..10 {
+            switched.push(trace.next().unwrap().unwrap());
+            assert_eq!(switched.last().unwrap().length <= 20, trace.is_literal());
+        }
+        assert_eq!(None, trace.next());
+
+        assert_eq!(literal, switched);
+        assert_eq!(7, literal[0].length);
+        assert_eq!(3073, literal[9].length);
+        assert_eq!(1588, literal[9].spread);
+    }
+
+    #[test]
+    fn _trace_csv() {
+        let (template, rules) = parse(EXAMPLE_INPUT).unwrap();
+        let mut csv = vec![];
+        Trace::new(&template, &rules, 2)
+            .write_csv(&mut csv)
+            .unwrap();
+        assert_eq!(
+            "step,length,spread,B,C,H,N\n1,7,1,2,2,1,2\n2,13,5,6,4,1,2\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn _trace_overflow() {
+        let (template, rules) = parse(EXAMPLE_INPUT).unwrap();
+        let mut trace = Trace::new(&template, &rules, 80);
+        for _ in 0..62 {
+            assert!(trace.next().unwrap().is_ok());
+        }
+        assert_eq!(Some(Err(Overflow { step: 63 })), trace.next());
+        assert_eq!(None, trace.next());
+
+        let mut csv = vec![];
+        let e = Trace::new(&template, &rules, 80)
+            .write_csv(&mut csv)
+            .unwrap_err();
+        assert_eq!("the element counts overflow on step 63", e.to_string());
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(63, csv.lines().count());
+        assert!(csv.contains(&format!("\n62,{},", (3u64 << 62) + 1)));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(3, 7, "a single element")),
+            parse("NNCB\n\nCH -> BB").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(parse(EXAMPLE_INPUT), parse(&input));
+    }
+}
@@ -0,0 +1,489 @@
+use common::{parse::lines, Grid, ParseError, Solution};
+use std::{error::Error, fmt, io};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Val = i64;
+
+/// A command as written in a program: a name and, for commands that take one, a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instr {
+    pub line: usize,
+    pub name: String,
+    pub val: Option<Val>,
+}
+
+/// Where the submarine is and which way it faces.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct State {
+    pub x: Val,
+    pub depth: Val,
+    pub aim: Val,
+    pub reversed: bool,
+}
+
+impl State {
+    /// 1 when facing forward, -1 after an odd number of turns.
+    pub fn heading(&self) -> Val {
+        if self.reversed {
+            -1
+        } else {
+            1
+        }
+    }
+
+    pub fn position(&self) -> (Val, Val) {
+        (self.x, self.depth)
+    }
+}
+
+/// What a command does to the submarine. Commands without a value are passed 0. `None` if
+/// the state would overflow.
+pub type Action = fn(&mut State, Val) -> Option<()>;
+
+/// Why a program couldn't be run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    /// This interpreter doesn't know `command`, which a program parsed by an interpreter with
+    /// other commands can contain.
+    UnknownCommand { line: usize, command: String },
+    /// `command` took the state out of the range of a `Val`.
+    Overflow { line: usize, command: String },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCommand { line, command } => {
+                write!(f, "line {}: unknown command `{}`", line, command)
+            }
+            Self::Overflow { line, command } => {
+                write!(f, "line {}: `{}` overflows the state", line, command)
+            }
+        }
+    }
+}
+
+impl Error for RunError {}
+
+#[derive(Copy, Clone)]
+pub struct Command {
+    pub name: &'static str,
+    pub takes_value: bool,
+    pub action: Action,
+}
+
+/// Runs programs against a table of commands.
+#[derive(Clone, Default)]
+pub struct Interpreter {
+    commands: Vec<Command>,
+}
+
+impl Interpreter {
+    /// An interpreter that knows no commands yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a command, replacing any command with the same name.
+    pub fn with(mut self, name: &'static str, takes_value: bool, action: Action) -> Self {
+        self.commands.retain(|c| c.name != name);
+        self.commands.push(Command {
+            name,
+            takes_value,
+            action,
+        });
+        self
+    }
+
+    /// `up` and `down` change the depth directly, the puzzle's part 1.
+    pub fn v1() -> Self {
+        Self::new()
+            .with("forward", true, |s, v| {
+                s.x = s.x.checked_add(s.heading().checked_mul(v)?)?;
+                Some(())
+            })
+            .with("down", true, |s, v| {
+                s.depth = s.depth.checked_add(v)?;
+                Some(())
+            })
+            .with("up", true, |s, v| {
+                s.depth = s.depth.checked_sub(v)?;
+                Some(())
+            })
+            .with("back", true, |s, v| {
+                s.x = s.x.checked_sub(s.heading().checked_mul(v)?)?;
+                Some(())
+            })
+            .with("turn", false, turn)
+            .with("reset", false, reset)
+    }
+
+    /// `up` and `down` change the aim, which moving then applies to the depth, the puzzle's
+    /// part 2.
+    pub fn v2() -> Self {
+        fn advance(s: &mut State, v: Val) -> Option<()> {
+            s.x = s.x.checked_add(v)?;
+            s.depth = s.depth.checked_add(v.checked_mul(s.aim)?)?;
+            Some(())
+        }
+        Self::new()
+            .with("forward", true, |s, v| {
+                advance(s, s.heading().checked_mul(v)?)
+            })
+            .with("down", true, |s, v| {
+                s.aim = s.aim.checked_add(v)?;
+                Some(())
+            })
+            .with("up", true, |s, v| {
+                s.aim = s.aim.checked_sub(v)?;
+                Some(())
+            })
+            .with("back", true, |s, v| {
+                advance(s, s.heading().checked_mul(v)?.checked_neg()?)
+            })
+            .with("turn", false, turn)
+            .with("reset", false, reset)
+    }
+
+    fn command(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+
+    /// The command names, like "`forward`, `down` or `up`".
+    fn expected(&self) -> String {
+        let names: Vec<_> = self
+            .commands
+            .iter()
+            .map(|c| format!("`{}`", c.name))
+            .collect();
+        match names.split_last() {
+            None => "no command".to_string(),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        }
+    }
+
+    /// Parses one command per line, checking each against this interpreter's commands.
+    pub fn parse(&self, s: &str) -> Result<Vec<Instr>, ParseError> {
+        lines(s)
+            .map(|l| {
+                let (name, val) = match l.text.split_once(' ') {
+                    Some((name, val)) => (name, Some(val)),
+                    None => (l.text, None),
+                };
+                let command = self
+                    .command(name)
+                    .ok_or_else(|| l.error_at(name, self.expected()))?;
+                let val = match (command.takes_value, val) {
+                    (true, Some(val)) => Some(l.parse::<Val>(val, "a number")?),
+                    (true, None) => return Err(l.error_at_end("a number")),
+                    (false, Some(val)) => return Err(l.error_at(val, "end of line")),
+                    (false, None) => None,
+                };
+                Ok(Instr {
+                    line: l.number,
+                    name: name.to_string(),
+                    val,
+                })
+            })
+            .collect()
+    }
+
+    /// The state after every command, starting from the surface.
+    ///
+    /// Fails on the first command this interpreter doesn't know or that overflows.
+    pub fn trajectory(&self, program: &[Instr]) -> Result<Vec<State>, RunError> {
+        let mut state = State::default();
+        program
+            .iter()
+            .map(|instr| {
+                let command =
+                    self.command(&instr.name)
+                        .ok_or_else(|| RunError::UnknownCommand {
+                            line: instr.line,
+                            command: instr.name.clone(),
+                        })?;
+                (command.action)(&mut state, instr.val.unwrap_or(0)).ok_or_else(|| {
+                    RunError::Overflow {
+                        line: instr.line,
+                        command: instr.name.clone(),
+                    }
+                })?;
+                Ok(state)
+            })
+            .collect()
+    }
+
+    /// The state after the whole program.
+    pub fn run(&self, program: &[Instr]) -> Result<State, RunError> {
+        Ok(self
+            .trajectory(program)?
+            .last()
+            .copied()
+            .unwrap_or_default())
+    }
+}
+
+/// Writes one row per command with the state after it.
+pub fn write_csv(
+    program: &[Instr],
+    trajectory: &[State],
+    mut out: impl io::Write,
+) -> io::Result<()> {
+    writeln!(out, "step,line,command,value,x,depth,aim,heading")?;
+    for (i, (instr, state)) in program.iter().zip(trajectory).enumerate() {
+        let val = instr.val.map(|v| v.to_string()).unwrap_or_default();
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            i + 1,
+            instr.line,
+            instr.name,
+            val,
+            state.x,
+            state.depth,
+            state.aim,
+            state.heading()
+        )?;
+    }
+    Ok(())
+}
+
+/// Plots the depth against the horizontal position on a `width` by `height` canvas, deeper
+/// further down. `#` marks where the submarine has been, starting from `S` at the surface,
+/// and `~` is the surface.
+pub fn depth_profile(trajectory: &[State], width: usize, height: usize) -> String {
+    assert!(
+        width > 0 && height > 0,
+        "requires a canvas of 1 by 1 or more"
+    );
+    let start = State::default();
+    let states = || std::iter::once(&start).chain(trajectory);
+    let (x_min, x_max) = bounds(states().map(|s| s.x));
+    let (d_min, d_max) = bounds(states().map(|s| s.depth));
+    let scale = |v: Val, min: Val, max: Val, cells: usize| {
+        ((v - min) as i128 * (cells - 1) as i128 / (max - min).max(1) as i128) as usize
+    };
+    let surface = scale(0, d_min, d_max, height);
+    let mut canvas = Grid::from_fn(width, height, |(_, y)| if y == surface { '~' } else { ' ' });
+    for s in trajectory {
+        canvas[(
+            scale(s.x, x_min, x_max, width),
+            scale(s.depth, d_min, d_max, height),
+        )] = '#';
+    }
+    canvas[(scale(0, x_min, x_max, width), surface)] = 'S';
+    canvas
+        .rows()
+        .map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n")
+        .collect()
+}
+
+fn turn(s: &mut State, _: Val) -> Option<()> {
+    s.reversed = !s.reversed;
+    Some(())
+}
+
+fn reset(s: &mut State, _: Val) -> Option<()> {
+    *s = State::default();
+    Some(())
+}
+
+fn bounds(vals: impl Iterator<Item = Val>) -> (Val, Val) {
+    vals.fold((Val::MAX, Val::MIN), |(min, max), v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+pub fn answer((pos_x, pos_y): (Val, Val)) -> i64 {
+    pos_x
+        .checked_mul(pos_y)
+        .unwrap_or_else(|| panic!("{} * {} overflows an i64", pos_x, pos_y))
+}
+
+pub fn parse(s: &str) -> Result<Vec<Instr>, ParseError> {
+    Interpreter::v1().parse(s)
+}
+
+pub fn eval_v1(moves: &[Instr]) -> (Val, Val) {
+    let state = Interpreter::v1().run(moves);
+    state.unwrap_or_else(|e| panic!("{}", e)).position()
+}
+
+pub fn eval_v2(moves: &[Instr]) -> (Val, Val) {
+    let state = Interpreter::v2().run(moves);
+    state.unwrap_or_else(|e| panic!("{}", e)).position()
+}
+
+pub fn answer_part1(moves: &[Instr]) -> i64 {
+    answer(eval_v1(moves))
+}
+
+pub fn answer_part2(moves: &[Instr]) -> i64 {
+    answer(eval_v2(moves))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Instr>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    #[test]
+    fn test() {
+        let moves = parse(TEST_INPUT).unwrap();
+        assert_eq!(answer(eval_v1(&moves)), 150);
+        assert_eq!(answer(eval_v2(&moves)), 900);
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                1,
+                "`forward`, `down`, `up`, `back`, `turn` or `reset`"
+            )),
+            parse("forward 5\nsideways 3").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 6, "end of line")),
+            parse("turn 3").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 5, "a number")),
+            parse("up 1\ndown").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _extended_commands() {
+        let moves = parse("forward 5\ndown 2\nturn\nforward 3\nback 1\nturn\nup 1").unwrap();
+        assert_eq!((3, 1), eval_v1(&moves));
+        assert_eq!((3, -4), eval_v2(&moves));
+
+        let moves = parse("forward 5\ndown 5\nreset\nforward 2\ndown 1").unwrap();
+        assert_eq!((2, 1), eval_v1(&moves));
+        assert_eq!((2, 0), eval_v2(&moves));
+    }
+
+    #[test]
+    fn _trajectory() {
+        let moves = parse(TEST_INPUT).unwrap();
+        let depths: Vec<_> = Interpreter::v2()
+            .trajectory(&moves)
+            .unwrap()
+            .iter()
+            .map(|s| s.depth)
+            .collect();
+        assert_eq!(vec![0, 0, 40, 40, 40, 60], depths);
+    }
+
+    #[test]
+    fn _write_csv() {
+        let moves = parse("forward 5\n\ndown 5\nturn").unwrap();
+        let trajectory = Interpreter::v2().trajectory(&moves).unwrap();
+        let mut csv = vec![];
+        write_csv(&moves, &trajectory, &mut csv).unwrap();
+        assert_eq!(
+            "step,line,command,value,x,depth,aim,heading\n\
+             1,1,forward,5,5,0,0,1\n\
+             2,3,down,5,5,0,5,1\n\
+             3,4,turn,,5,0,5,-1\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn _depth_profile() {
+        let moves = parse(TEST_INPUT).unwrap();
+        let trajectory = Interpreter::v2().trajectory(&moves).unwrap();
+        assert_eq!(
+            "S~~~~#~~~~~~~~~~\n\
+             \n\
+             \x20            #\n\
+             \x20              #\n",
+            depth_profile(&trajectory, 16, 4)
+        );
+        assert_eq!("S\n", depth_profile(&[], 1, 1));
+    }
+
+    #[test]
+    fn _custom_commands() {
+        let diver = Interpreter::new()
+            .with("forward", true, |s, v| {
+                s.x = s.x.checked_add(v)?;
+                Some(())
+            })
+            .with("dive", false, |s, _| {
+                s.depth = s.depth.checked_mul(2)?;
+                Some(())
+            })
+            .with("down", true, |s, v| {
+                s.depth = s.depth.checked_add(v)?;
+                Some(())
+            });
+        let moves = diver.parse("down 3\ndive\nforward 4\ndive").unwrap();
+        assert_eq!((4, 12), diver.run(&moves).unwrap().position());
+
+        assert_eq!(
+            Err(RunError::UnknownCommand {
+                line: 2,
+                command: "up".to_string()
+            }),
+            diver.run(&parse("forward 1\nup 2").unwrap())
+        );
+    }
+
+    #[test]
+    fn _overflow() {
+        let moves = parse("forward 9223372036854775807\nforward 1").unwrap();
+        let overflow = Err(RunError::Overflow {
+            line: 2,
+            command: "forward".to_string(),
+        });
+        assert_eq!(overflow, Interpreter::v1().run(&moves));
+        assert_eq!(overflow, Interpreter::v2().run(&moves));
+
+        let moves = parse("down 3037000500\nforward 3037000500").unwrap();
+        assert!(Interpreter::v1().run(&moves).is_ok());
+        assert_eq!(
+            "line 2: `forward` overflows the state",
+            Interpreter::v2().run(&moves).unwrap_err().to_string()
+        );
+        let moves = parse("turn\nback -9223372036854775808").unwrap();
+        assert!(Interpreter::v1().run(&moves).is_err());
+        assert!(Interpreter::v2().run(&moves).is_err());
+    }
+}
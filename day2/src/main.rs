@@ -1,120 +1,39 @@
-#![feature(test)]
-extern crate test;
+use day2::{
+    answer_part1, answer_part2, depth_profile, parse, write_csv, Interpreter, EMBEDDED_INPUT,
+};
+use std::{io, process};
 
-type Val = i64;
-
-#[derive(Copy, Clone, Debug)]
-enum Instr {
-    Forward(Val),
-    Down(Val),
-    Up(Val),
-}
-fn answer((pos_x, pos_y): (Val, Val)) -> i64 {
-    (pos_x as i64) * (pos_y as i64)
-}
-
-fn parse(s: &str) -> Vec<Instr> {
-    s.split("\n")
-        .flat_map(|l| {
-            l.split_once(" ").map(|(tag, val)| {
-                let val = val.parse::<Val>().expect("input not well formed");
-                match tag {
-                    "forward" => Instr::Forward(val),
-                    "down" => Instr::Down(val),
-                    "up" => Instr::Up(val),
-                    _ => panic!("input not well formed"),
-                }
-            })
+fn main() {
+    let input = common::input::from_args(EMBEDDED_INPUT).expect("failed to read input");
+    let moves = parse(&input).unwrap_or_else(|e| {
+        eprintln!("failed to parse input: {}", e);
+        process::exit(1)
+    });
+    let trajectory = |interpreter: Interpreter| {
+        interpreter.trajectory(&moves).unwrap_or_else(|e| {
+            eprintln!("failed to run the program: {}", e);
+            process::exit(1)
         })
-        .collect()
-}
-
-fn eval_v1(moves: &[Instr]) -> (Val, Val) {
-    let mut pos = (0, 0);
-    for &inst in moves.iter() {
-        match inst {
-            Instr::Forward(val) => {
-                pos.0 += val;
-            }
-            Instr::Down(val) => {
-                pos.1 += val;
-            }
-            Instr::Up(val) => {
-                pos.1 -= val;
-            }
-        }
-    }
-    pos
-}
-
-fn eval_v2(moves: &[Instr]) -> (Val, Val) {
-    let mut pos = (0, 0);
-    let mut aim = 0;
-    for &inst in moves.iter() {
-        match inst {
-            Instr::Forward(val) => {
-                pos.0 += val;
-                pos.1 += val * aim;
-            }
-            Instr::Down(val) => {
-                aim += val;
-            }
-            Instr::Up(val) => {
-                aim -= val;
-            }
+    };
+    let semantics = |flag: &str, version: String| match version.as_str() {
+        "" | "v2" => Interpreter::v2(),
+        "v1" => Interpreter::v1(),
+        _ => {
+            eprintln!("expected --{}=v1 or --{}=v2", flag, flag);
+            process::exit(1)
         }
+    };
+    if let Some(version) = common::input::flag("trajectory") {
+        let trajectory = trajectory(semantics("trajectory", version));
+        write_csv(&moves, &trajectory, io::stdout().lock())
+            .expect("failed to write the trajectory");
+        return;
     }
-    pos
-}
-
-fn main() {
-    let moves = parse(&include_str!("inputs"));
-    println!(
-        "Part1 = {}\nPart2 = {}",
-        answer(eval_v1(&moves)),
-        answer(eval_v2(&moves))
-    );
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &'static str = "forward 5
-down 5
-forward 8
-up 3
-down 8
-forward 2";
-
-    #[test]
-    fn test() {
-        let moves = parse(TEST_INPUT);
-        assert_eq!(answer(eval_v1(&moves)), 150);
-        assert_eq!(answer(eval_v2(&moves)), 900);
-    }
-
-    #[bench]
-    fn _parse(b: &mut test::Bencher) {
-        let input = include_str!("inputs");
-        b.iter(|| {
-            test::black_box(parse(input));
-        });
-    }
-
-    #[bench]
-    fn _eval_v1(b: &mut test::Bencher) {
-        let moves = parse(include_str!("inputs"));
-        b.iter(|| {
-            test::black_box(eval_v1(&moves));
-        });
-    }
-
-    #[bench]
-    fn _eval_v2(b: &mut test::Bencher) {
-        let moves = parse(include_str!("inputs"));
-        b.iter(|| {
-            test::black_box(eval_v2(&moves));
-        });
+    if let Some(version) = common::input::flag("profile") {
+        let trajectory = trajectory(semantics("profile", version));
+        print!("{}", depth_profile(&trajectory, 80, 24));
+        return;
     }
+    println!("Part 1 = {}", answer_part1(&moves));
+    println!("Part 2 = {}", answer_part2(&moves));
 }
@@ -0,0 +1,477 @@
+use common::{parse::lines, ParseError, Solution};
+use std::{
+    error::Error,
+    fmt::{self, Write},
+    ops::Not,
+};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+const WORD: usize = u64::BITS as usize;
+
+/// A row of the report packed into 64 bit words, the leftmost column in the highest bit of
+/// the first word. Bits past the end of the row are always 0.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// `len` zeros.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The bit in column `i`, counting from the left.
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "column {} is past the end of the row", i);
+        self.words[i / WORD] & mask(i) != 0
+    }
+
+    pub fn set(&mut self, i: usize, bit: bool) {
+        assert!(i < self.len, "column {} is past the end of the row", i);
+        if bit {
+            self.words[i / WORD] |= mask(i);
+        } else {
+            self.words[i / WORD] &= !mask(i);
+        }
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(WORD) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+}
+
+fn mask(i: usize) -> u64 {
+    1 << (WORD - 1 - i % WORD)
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let mut xs = BitVec::new(0);
+        for bit in bits {
+            xs.push(bit);
+        }
+        xs
+    }
+}
+
+impl Not for &BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> BitVec {
+        let mut words: Vec<_> = self.words.iter().map(|w| !w).collect();
+        if let Some(last) = words.last_mut() {
+            // Keep the bits past the end 0.
+            *last &= u64::MAX << ((WORD - self.len % WORD) % WORD);
+        }
+        BitVec {
+            words,
+            len: self.len,
+        }
+    }
+}
+
+/// The bits as `0`s and `1`s, left to right.
+impl fmt::Display for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.len {
+            f.write_char(if self.get(i) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses one row per line. Every row must be as wide as the first.
+pub fn parse(s: &str) -> Result<Vec<BitVec>, ParseError> {
+    let mut rows: Vec<BitVec> = vec![];
+    for l in lines(s) {
+        let width = rows.first().map(|r| r.len());
+        let mut row = BitVec::new(0);
+        for (i, c) in l.text.char_indices() {
+            if width == Some(row.len()) {
+                return Err(l.error_at(&l.text[i..], "end of the row"));
+            }
+            match c {
+                '0' => row.push(false),
+                '1' => row.push(true),
+                _ => return Err(l.error_at(&l.text[i..], "`0` or `1`")),
+            }
+        }
+        if width.is_some_and(|w| row.len() < w) {
+            return Err(l.error_at_end("`0` or `1`"));
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// How many rows have a 1 in each column, left to right.
+///
+/// Counts a whole word of columns at a time: `counters[k]` holds bit `k` of every column's
+/// count, and adding a row ripples its bits through them like a binary adder.
+pub fn column_counts<'a>(rows: impl IntoIterator<Item = &'a BitVec>) -> Vec<usize> {
+    let mut rows = rows.into_iter().peekable();
+    let Some(len) = rows.peek().map(|r| r.len()) else {
+        return vec![];
+    };
+    let mut counters = vec![Vec::<u64>::new(); len.div_ceil(WORD)];
+    for row in rows {
+        assert_eq!(len, row.len(), "every row must be equally wide");
+        for (counter, &word) in counters.iter_mut().zip(&row.words) {
+            let mut carry = word;
+            for plane in counter.iter_mut() {
+                if carry == 0 {
+                    break;
+                }
+                let next = *plane & carry;
+                *plane ^= carry;
+                carry = next;
+            }
+            if carry != 0 {
+                counter.push(carry);
+            }
+        }
+    }
+    (0..len)
+        .map(|i| {
+            let planes = &counters[i / WORD];
+            planes
+                .iter()
+                .enumerate()
+                .map(|(k, plane)| ((plane & mask(i) != 0) as usize) << k)
+                .sum()
+        })
+        .collect()
+}
+
+/// Why a rate or a rating couldn't be found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticError {
+    /// The report has no rows.
+    NoSamples,
+    /// Filtering on bit `column`, counting from 0 on the left, dropped every row.
+    Exhausted { column: usize },
+    /// `rows` identical rows were left after filtering on every bit.
+    Ambiguous { rows: usize },
+}
+
+impl fmt::Display for DiagnosticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSamples => write!(f, "requires 1 or more samples"),
+            Self::Exhausted { column } => {
+                write!(f, "no rows are left after filtering on bit {}", column)
+            }
+            Self::Ambiguous { rows } => write!(
+                f,
+                "{} identical rows are left after filtering on every bit",
+                rows
+            ),
+        }
+    }
+}
+
+impl Error for DiagnosticError {}
+
+/// The most common bit in every column, 1 on a tie.
+pub fn gamma_rate(lines: &[BitVec]) -> Result<BitVec, DiagnosticError> {
+    if lines.is_empty() {
+        return Err(DiagnosticError::NoSamples);
+    }
+    Ok(column_counts(lines)
+        .into_iter()
+        .map(|ones| ones * 2 >= lines.len())
+        .collect())
+}
+
+pub fn epsilon_rate(gamma: &BitVec) -> BitVec {
+    !gamma
+}
+
+/// The row as a binary number, or `None` if it doesn't fit in a `u64`.
+pub fn to_decimal(xs: &BitVec) -> Option<u64> {
+    let (high, low) = xs.words.split_at(xs.words.len().saturating_sub(1));
+    if high.iter().any(|&w| w != 0) {
+        return None;
+    }
+    let Some(&last) = low.first() else {
+        return Some(0);
+    };
+    Some(last >> ((WORD - xs.len % WORD) % WORD))
+}
+
+/// Filters the rows bit by bit, from the left, until one is left.
+///
+/// For each bit `criterion(ones * 2, rows)` says whether to keep the rows with a 1 there,
+/// from how many of the remaining rows have one. When exactly half of them do, `tie` decides
+/// instead, given the bit's column.
+pub fn rating<C, T>(
+    lines: &[BitVec],
+    mut criterion: C,
+    mut tie: T,
+) -> Result<BitVec, DiagnosticError>
+where
+    C: FnMut(usize, usize) -> bool,
+    T: FnMut(usize) -> bool,
+{
+    let mut lines: Vec<_> = lines.iter().collect();
+    let width = lines.first().ok_or(DiagnosticError::NoSamples)?.len();
+    for i in 0..width {
+        if lines.len() == 1 {
+            break;
+        }
+        let ones_count = lines.iter().filter(|x| x.get(i)).count();
+        let keep = if ones_count * 2 == lines.len() {
+            tie(i)
+        } else {
+            criterion(ones_count * 2, lines.len())
+        };
+        lines.retain(|l| l.get(i) == keep);
+        if lines.is_empty() {
+            return Err(DiagnosticError::Exhausted { column: i });
+        }
+    }
+    match lines[..] {
+        [rating] => Ok(rating.clone()),
+        _ => Err(DiagnosticError::Ambiguous { rows: lines.len() }),
+    }
+}
+
+/// Keeps the most common bit, 1 on a tie.
+pub fn oxygen_generator_rating(lines: &[BitVec]) -> Result<BitVec, DiagnosticError> {
+    rating(lines, |l, r| l >= r, |_| true)
+}
+
+/// Keeps the least common bit, 0 on a tie.
+pub fn co2_scrubber_rating(lines: &[BitVec]) -> Result<BitVec, DiagnosticError> {
+    rating(lines, |l, r| l < r, |_| false)
+}
+
+/// The product of two rates as decimal numbers, which must fit in a `u64`.
+fn product(a: &BitVec, b: &BitVec) -> u64 {
+    to_decimal(a)
+        .zip(to_decimal(b))
+        .and_then(|(a, b)| a.checked_mul(b))
+        .unwrap_or_else(|| panic!("{} times {} overflows a u64", a, b))
+}
+
+pub fn answer_part1(lines: &[BitVec]) -> u64 {
+    let gamma = gamma_rate(lines).unwrap_or_else(|e| panic!("{}", e));
+    product(&gamma, &epsilon_rate(&gamma))
+}
+
+pub fn answer_part2(lines: &[BitVec]) -> u64 {
+    let oxygen = oxygen_generator_rating(lines).unwrap_or_else(|e| panic!("{}", e));
+    let co2 = co2_scrubber_rating(lines).unwrap_or_else(|e| panic!("{}", e));
+    product(&oxygen, &co2)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<BitVec>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+    #[test]
+    fn tests_part1() {
+        let lines = parse(TEST_INPUT).unwrap();
+        let gamma = gamma_rate(&lines).unwrap();
+        assert_eq!(Some(22), to_decimal(&gamma));
+        assert_eq!(Some(9), to_decimal(&epsilon_rate(&gamma)));
+        assert_eq!(198, answer_part1(&lines));
+    }
+
+    #[test]
+    fn tests_part2() {
+        let lines = parse(TEST_INPUT).unwrap();
+        assert_eq!(
+            Some(23),
+            to_decimal(&oxygen_generator_rating(&lines).unwrap())
+        );
+        assert_eq!(Some(10), to_decimal(&co2_scrubber_rating(&lines).unwrap()));
+        assert_eq!(230, answer_part2(&lines));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 3, "`0` or `1`")),
+            parse("00100\n11210").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 6, "end of the row")),
+            parse("00100\n111101").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(3, 4, "`0` or `1`")),
+            parse("00100\n11110\n101").map(|_| ())
+        );
+    }
+
+    /// Rows wider than a word, from a small linear congruential generator.
+    fn wide_rows(width: usize, count: usize) -> String {
+        let mut seed = 3u32;
+        (0..count)
+            .map(|_| {
+                (0..width)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                        if seed >> 31 == 1 {
+                            '1'
+                        } else {
+                            '0'
+                        }
+                    })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn _wide_rows() {
+        let input = wide_rows(150, 300);
+        let rows = parse(&input).unwrap();
+        assert_eq!(3, rows[0].words().len());
+        assert_eq!(input.lines().next().unwrap(), rows[0].to_string());
+
+        let counts = column_counts(&rows);
+        for (i, &count) in counts.iter().enumerate() {
+            assert_eq!(rows.iter().filter(|r| r.get(i)).count(), count);
+        }
+
+        let gamma = gamma_rate(&rows).unwrap();
+        let epsilon = epsilon_rate(&gamma);
+        assert_eq!(150, epsilon.len());
+        assert!((0..150).all(|i| gamma.get(i) != epsilon.get(i)));
+        assert_eq!(0, epsilon.words().last().unwrap() & (u64::MAX >> 22));
+        assert_eq!(None, to_decimal(&gamma));
+    }
+
+    #[test]
+    fn _rating_errors() {
+        assert_eq!(Err(DiagnosticError::NoSamples), gamma_rate(&[]));
+        assert_eq!(
+            Err(DiagnosticError::NoSamples),
+            oxygen_generator_rating(&[])
+        );
+
+        let lines = parse("101\n101\n100").unwrap();
+        assert_eq!(
+            Err(DiagnosticError::Ambiguous { rows: 2 }),
+            oxygen_generator_rating(&lines)
+        );
+        assert_eq!(
+            "2 identical rows are left after filtering on every bit",
+            oxygen_generator_rating(&lines).unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(DiagnosticError::Exhausted { column: 0 }),
+            co2_scrubber_rating(&lines)
+        );
+
+        let lines = parse("101\n100\n001").unwrap();
+        assert_eq!(
+            Err(DiagnosticError::Exhausted { column: 1 }),
+            rating(&lines, |_, _| true, |_| true)
+        );
+    }
+
+    #[test]
+    fn _rating_criteria() {
+        let lines = parse(TEST_INPUT).unwrap();
+        // The most common bit, but 0 on a tie.
+        let rating = rating(&lines, |l, r| l > r, |_| false).unwrap();
+        assert_eq!("10110", rating.to_string());
+
+        // Alternate between the most and the least common bit.
+        let alternating = super::rating(
+            &lines,
+            {
+                let mut most = false;
+                move |l, r| {
+                    most = !most;
+                    (l > r) == most
+                }
+            },
+            |column| column % 2 == 0,
+        );
+        assert_eq!("11100", alternating.unwrap().to_string());
+        assert_eq!(
+            Ok(lines[0].clone()),
+            super::rating(&lines[..1], |_, _| false, |_| false)
+        );
+    }
+
+    #[test]
+    fn _to_decimal() {
+        let row = |s: &str| parse(s).unwrap().remove(0);
+        assert_eq!(Some(u64::MAX), to_decimal(&row(&"1".repeat(64))));
+        assert_eq!(Some(5), to_decimal(&row(&format!("{}101", "0".repeat(80)))));
+        assert_eq!(None, to_decimal(&row(&format!("1{}", "0".repeat(64)))));
+        assert_eq!(Some(0), to_decimal(&BitVec::new(0)));
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = TEST_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(198, answer_part1(&parse(&input).unwrap()));
+    }
+}
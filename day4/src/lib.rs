@@ -0,0 +1,647 @@
+use common::{grid::Point, parse::normalize, Grid, ParseError, Solution};
+use nom::{
+    branch::alt,
+    character::complete::{char, space0, u32},
+    combinator::{all_consuming, consumed, cut, eof, map, not, peek},
+    error::{context, VerboseError, VerboseErrorKind},
+    multi,
+    sequence::{self, preceded, terminated},
+    Finish, IResult,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+/// How a board can win, besides a full row or column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rules {
+    /// Either diagonal of a square board wins too.
+    pub diagonals: bool,
+}
+
+/// A full line of marked numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+    /// From the top left to the bottom right.
+    Diagonal,
+    /// From the top right to the bottom left.
+    AntiDiagonal,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Row(y) => write!(f, "row {}", y),
+            Line::Column(x) => write!(f, "column {}", x),
+            Line::Diagonal => write!(f, "diagonal"),
+            Line::AntiDiagonal => write!(f, "anti-diagonal"),
+        }
+    }
+}
+
+/// A bingo board of any size and the numbers marked on it so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    numbers: Grid<u32>,
+    /// One bit per cell, row by row.
+    marked: Vec<u64>,
+}
+
+impl Board {
+    /// A board with nothing marked.
+    pub fn new(numbers: Grid<u32>) -> Self {
+        let cells = numbers.width() * numbers.height();
+        Self {
+            numbers,
+            marked: vec![0; cells.div_ceil(64)],
+        }
+    }
+
+    pub fn numbers(&self) -> &Grid<u32> {
+        &self.numbers
+    }
+
+    fn bit(&self, (x, y): Point) -> (usize, u64) {
+        let i = y * self.numbers.width() + x;
+        (i / 64, 1 << (i % 64))
+    }
+
+    pub fn is_marked(&self, p: Point) -> bool {
+        let (word, bit) = self.bit(p);
+        self.marked[word] & bit != 0
+    }
+
+    pub fn mark_at(&mut self, p: Point) {
+        let (word, bit) = self.bit(p);
+        self.marked[word] |= bit;
+    }
+
+    /// Marks every cell holding `n`, returning whether there were any.
+    pub fn mark(&mut self, n: u32) -> bool {
+        let cells: Vec<_> = self
+            .numbers
+            .points()
+            .filter(|&p| self.numbers[p] == n)
+            .collect();
+        for &p in &cells {
+            self.mark_at(p);
+        }
+        !cells.is_empty()
+    }
+
+    /// A fully marked line, rows first, then columns, then diagonals if `rules` allow them.
+    pub fn winning_line(&self, rules: Rules) -> Option<Line> {
+        let (width, height) = (self.numbers.width(), self.numbers.height());
+        let diagonals = if rules.diagonals && width == height && width > 0 {
+            &[Line::Diagonal, Line::AntiDiagonal][..]
+        } else {
+            &[]
+        };
+        (0..height)
+            .map(Line::Row)
+            .chain((0..width).map(Line::Column))
+            .chain(diagonals.iter().copied())
+            .find(|&line| self.cells(line).iter().all(|&p| self.is_marked(p)))
+    }
+
+    /// A fully marked line through `p`, like `winning_line` but only checking the lines
+    /// that a newly marked cell can complete.
+    pub fn winning_line_through(&self, (x, y): Point, rules: Rules) -> Option<Line> {
+        let (width, height) = (self.numbers.width(), self.numbers.height());
+        let square = rules.diagonals && width == height;
+        [
+            Some(Line::Row(y)),
+            Some(Line::Column(x)),
+            (square && x == y).then_some(Line::Diagonal),
+            (square && x + y + 1 == width).then_some(Line::AntiDiagonal),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|&line| self.cells(line).iter().all(|&p| self.is_marked(p)))
+    }
+
+    /// The positions along `line`.
+    pub fn cells(&self, line: Line) -> Vec<Point> {
+        let (width, height) = (self.numbers.width(), self.numbers.height());
+        match line {
+            Line::Row(y) => (0..width).map(|x| (x, y)).collect(),
+            Line::Column(x) => (0..height).map(|y| (x, y)).collect(),
+            Line::Diagonal => (0..width.min(height)).map(|i| (i, i)).collect(),
+            Line::AntiDiagonal => (0..width.min(height)).map(|i| (width - 1 - i, i)).collect(),
+        }
+    }
+
+    /// The sum of the numbers that aren't marked.
+    pub fn unmarked_sum(&self) -> u64 {
+        self.numbers
+            .points()
+            .filter(|&p| !self.is_marked(p))
+            .map(|p| self.numbers[p] as u64)
+            .sum()
+    }
+}
+
+pub type Input = (Vec<u32>, Vec<Board>);
+
+type ParseResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// A row of numbers, each with the text it was parsed from.
+pub fn parse_row(s: &str) -> ParseResult<'_, Vec<(&str, u32)>> {
+    let number = || context("a number", consumed(u32));
+    terminated(
+        multi::many1(preceded(space0, number())),
+        context(
+            "a number",
+            peek(preceded(space0, alt((eof, map(char('\n'), |_| ""))))),
+        ),
+    )(s)
+}
+
+pub fn parse_board(s: &str) -> ParseResult<'_, Vec<Vec<(&str, u32)>>> {
+    context(
+        "a board",
+        multi::separated_list1(terminated(char('\n'), not(char('\n'))), cut(parse_row)),
+    )(s)
+}
+
+fn blank_lines(s: &str) -> ParseResult<'_, ()> {
+    map(preceded(char('\n'), multi::many1_count(char('\n'))), |_| ())(s)
+}
+
+/// Parses the draws and the boards. Every row of a board must be as wide as its first.
+pub fn parse(s: &str) -> Result<Input, ParseError> {
+    let s = &normalize(s);
+    let inputs = context(
+        "comma separated numbers",
+        multi::separated_list1(char(','), u32),
+    );
+    let boards = multi::separated_list1(blank_lines, cut(parse_board));
+
+    let (_, (inputs, boards)) = all_consuming(sequence::tuple((
+        inputs,
+        preceded(context("a blank line", blank_lines), boards),
+    )))(s)
+    .finish()
+    .map_err(|e| to_parse_error(s, e))?;
+
+    let offset = |part: &str| part.as_ptr() as usize - s.as_ptr() as usize;
+    let boards = boards
+        .into_iter()
+        .map(|rows| {
+            let width = rows[0].len();
+            let expected = || format!("a row of {} numbers", width);
+            for row in &rows {
+                if let Some(&(extra, _)) = row.get(width) {
+                    return Err(ParseError::at_offset(s, offset(extra), expected()));
+                }
+                if row.len() < width {
+                    let (last, _) = row[row.len() - 1];
+                    return Err(ParseError::at_offset(
+                        s,
+                        offset(last) + last.len(),
+                        expected(),
+                    ));
+                }
+            }
+            let numbers = Grid::from_fn(width, rows.len(), |(x, y)| rows[y][x].1);
+            Ok(Board::new(numbers))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((inputs, boards))
+}
+
+fn to_parse_error(s: &str, e: VerboseError<&str>) -> ParseError {
+    let rest = e.errors.first().map_or("", |&(rest, _)| rest);
+    let expected = e
+        .errors
+        .iter()
+        .find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(c) => Some(*c),
+            _ => None,
+        })
+        .unwrap_or("end of input");
+    ParseError::at_offset(s, s.len() - rest.len(), expected)
+}
+
+/// A board winning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinEvent {
+    /// The index of the winning number in the draws.
+    pub draw: usize,
+    pub number: u32,
+    /// The index of the board in the input.
+    pub board: usize,
+    pub line: Line,
+    /// The sum of the board's unmarked numbers times the winning number.
+    pub score: u64,
+}
+
+/// Draws the numbers one by one, yielding every board as it wins. Boards winning on the same
+/// draw come in the order of the input.
+pub struct BingoSimulation<'a> {
+    draws: &'a [u32],
+    boards: Vec<Board>,
+    rules: Rules,
+    /// Every cell holding a number, as a board index and a position.
+    index: HashMap<u32, Vec<(usize, Point)>>,
+    won: Vec<bool>,
+    next_draw: usize,
+    /// Winners of the last draw that haven't been yielded yet.
+    pending: VecDeque<WinEvent>,
+}
+
+impl<'a> BingoSimulation<'a> {
+    pub fn new(draws: &'a [u32], boards: Vec<Board>) -> Self {
+        let mut index: HashMap<_, Vec<_>> = HashMap::new();
+        for (i, board) in boards.iter().enumerate() {
+            for p in board.numbers().points() {
+                index.entry(board.numbers()[p]).or_default().push((i, p));
+            }
+        }
+        Self {
+            draws,
+            won: vec![false; boards.len()],
+            boards,
+            rules: Rules::default(),
+            index,
+            next_draw: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The boards with everything drawn so far marked.
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+
+    /// Marks `number` everywhere and queues the boards it completes a line on.
+    fn draw(&mut self, draw: usize, number: u32) {
+        let cells = self.index.get(&number).map_or(&[][..], |cells| cells);
+        for &(i, p) in cells {
+            if !self.won[i] {
+                self.boards[i].mark_at(p);
+            }
+        }
+        for &(i, p) in cells {
+            if self.won[i] {
+                continue;
+            }
+            if let Some(line) = self.boards[i].winning_line_through(p, self.rules) {
+                self.won[i] = true;
+                self.pending.push_back(WinEvent {
+                    draw,
+                    number,
+                    board: i,
+                    line,
+                    score: self.boards[i].unmarked_sum() * number as u64,
+                });
+            }
+        }
+    }
+}
+
+impl Iterator for BingoSimulation<'_> {
+    type Item = WinEvent;
+
+    fn next(&mut self) -> Option<WinEvent> {
+        while self.pending.is_empty() {
+            if self.won.iter().all(|&won| won) {
+                return None;
+            }
+            let draw = self.next_draw;
+            let &number = self.draws.get(draw)?;
+            self.next_draw += 1;
+            self.draw(draw, number);
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Every board in the order they win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranking {
+    /// The wins of each draw that had any, in the order of the input within a draw.
+    pub rounds: Vec<Vec<WinEvent>>,
+    /// The boards that still hadn't won when the draws ran out.
+    pub losers: Vec<usize>,
+}
+
+impl Ranking {
+    pub fn new(bingo: BingoSimulation) -> Self {
+        let mut boards = vec![false; bingo.boards().len()];
+        let mut rounds: Vec<Vec<WinEvent>> = vec![];
+        for win in bingo {
+            boards[win.board] = true;
+            match rounds.last_mut() {
+                Some(round) if round[0].draw == win.draw => round.push(win),
+                _ => rounds.push(vec![win]),
+            }
+        }
+        let losers = (0..boards.len()).filter(|&i| !boards[i]).collect();
+        Self { rounds, losers }
+    }
+
+    pub fn first(&self) -> Option<&WinEvent> {
+        self.rounds.first()?.first()
+    }
+
+    /// The last board to win. Of several boards winning on the last draw, the last one in
+    /// the input.
+    pub fn last(&self) -> Option<&WinEvent> {
+        self.rounds.last()?.last()
+    }
+}
+
+/// A table with a row per board, numbering draws and boards from 1. Boards winning on the
+/// same draw share a rank.
+impl fmt::Display for Ranking {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:>5}  {:>6}  {:>5}  {:<13}  {:>10}",
+            "rank", "draw", "number", "board", "line", "score"
+        )?;
+        let mut rank = 1;
+        for round in &self.rounds {
+            for win in round {
+                writeln!(
+                    f,
+                    "{:>4}  {:>5}  {:>6}  {:>5}  {:<13}  {:>10}",
+                    rank,
+                    win.draw + 1,
+                    win.number,
+                    win.board + 1,
+                    win.line.to_string(),
+                    win.score
+                )?;
+            }
+            rank += round.len();
+        }
+        for board in &self.losers {
+            writeln!(
+                f,
+                "{:>4}  {:>5}  {:>6}  {:>5}  {:<13}  {:>10}",
+                "-",
+                "-",
+                "-",
+                board + 1,
+                "-",
+                "-"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn ranking((inputs, boards): &Input) -> Ranking {
+    Ranking::new(BingoSimulation::new(inputs, boards.clone()))
+}
+
+pub fn answer_part1((inputs, boards): &Input) -> u64 {
+    let mut bingo = BingoSimulation::new(inputs, boards.clone());
+    bingo.next().expect("someone should win").score
+}
+
+pub fn answer_part2(input: &Input) -> u64 {
+    ranking(input).last().expect("someone should win").score
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Input;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUTS: &str =
+        "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn test_part1() {
+        let inputs = parse(EXAMPLE_INPUTS).unwrap();
+        assert_eq!(4_512, answer_part1(&inputs));
+    }
+    #[test]
+    fn test_part2() {
+        let inputs = parse(EXAMPLE_INPUTS).unwrap();
+        assert_eq!(1_924, answer_part2(&inputs));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(4, 7, "a number")),
+            parse("7,4\n\n22 13 17 11  0\n 8  2 x  4 24\n").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(4, 12, "a row of 5 numbers")),
+            parse("7,4\n\n22 13 17 11  0\n 8  2 23  4\n").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(6, 14, "a row of 4 numbers")),
+            parse("7,4\n\n1 2\n\n22 13 17 11\n 8  2 23  4  0\n").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _any_size() {
+        let input = "300,7,1000000,4\n\n1000000 300 5\n7 4 9\n\n255 300\n1 2";
+        let (inputs, boards) = parse(input).unwrap();
+        assert_eq!(
+            (3, 2),
+            (boards[0].numbers().width(), boards[0].numbers().height())
+        );
+        assert_eq!(
+            (2, 2),
+            (boards[1].numbers().width(), boards[1].numbers().height())
+        );
+        let mut bingo = BingoSimulation::new(&inputs, boards);
+        let win = bingo.next().unwrap();
+        assert_eq!((2, 0, Line::Column(0)), (win.draw, win.board, win.line));
+        assert_eq!(18_000_000, win.score);
+        assert_eq!(None, bingo.next());
+    }
+
+    #[test]
+    fn _win_events() {
+        let (inputs, boards) = parse(EXAMPLE_INPUTS).unwrap();
+        let wins: Vec<_> = BingoSimulation::new(&inputs, boards).collect();
+        assert_eq!(
+            vec![
+                WinEvent {
+                    draw: 11,
+                    number: 24,
+                    board: 2,
+                    line: Line::Row(0),
+                    score: 4_512
+                },
+                WinEvent {
+                    draw: 13,
+                    number: 16,
+                    board: 0,
+                    line: Line::Row(2),
+                    score: 2_192
+                },
+                WinEvent {
+                    draw: 14,
+                    number: 13,
+                    board: 1,
+                    line: Line::Column(2),
+                    score: 1_924
+                },
+            ],
+            wins
+        );
+    }
+
+    #[test]
+    fn _same_draw() {
+        // Both boards win on 3, then the draws run out before the last one wins.
+        let (inputs, boards) = parse("1,2,3\n\n9 3\n5 1\n\n3 8\n2 7\n\n5 6\n7 8").unwrap();
+        let wins: Vec<_> = BingoSimulation::new(&inputs, boards)
+            .map(|w| (w.draw, w.board, w.line))
+            .collect();
+        assert_eq!(vec![(2, 0, Line::Column(1)), (2, 1, Line::Column(0))], wins);
+
+        let draws = vec![4; 100_000];
+        let (_, boards) = parse("1\n\n4 5\n6 7").unwrap();
+        assert_eq!(None, BingoSimulation::new(&draws, boards).next());
+    }
+
+    #[test]
+    fn _ranking() {
+        let input = parse(EXAMPLE_INPUTS).unwrap();
+        let ranking = ranking(&input);
+        assert_eq!(3, ranking.rounds.len());
+        assert_eq!(Some(4_512), ranking.first().map(|w| w.score));
+        assert_eq!(Some(1_924), ranking.last().map(|w| w.score));
+        assert_eq!(
+            "rank   draw  number  board  line                score\n\
+             \x20  1     12      24      3  row 0                4512\n\
+             \x20  2     14      16      1  row 2                2192\n\
+             \x20  3     15      13      2  column 2             1924\n",
+            ranking.to_string()
+        );
+    }
+
+    #[test]
+    fn _ranking_ties() {
+        let input = "1,2,3,4\n\n9 3\n5 1\n\n3 8\n2 7\n\n1 2\n6 6\n\n5 6\n7 8";
+        let ranking = ranking(&parse(input).unwrap());
+        let boards: Vec<Vec<_>> = ranking
+            .rounds
+            .iter()
+            .map(|round| round.iter().map(|w| w.board).collect())
+            .collect();
+        assert_eq!(vec![vec![2], vec![0, 1]], boards);
+        assert_eq!(vec![3], ranking.losers);
+        assert_eq!(
+            "rank   draw  number  board  line                score\n\
+             \x20  1      2       2      3  row 0                  24\n\
+             \x20  2      3       3      1  column 1               42\n\
+             \x20  2      3       3      2  column 0               45\n\
+             \x20  -      -       -      4  -                       -\n",
+            ranking.to_string()
+        );
+    }
+
+    #[test]
+    fn _marking() {
+        let (_, boards) = parse("0\n\n255 0\n3 4").unwrap();
+        let mut board = boards[0].clone();
+        assert!(board.mark(255));
+        assert!(!board.mark(7));
+        assert!(board.is_marked((0, 0)) && !board.is_marked((1, 0)));
+        assert_eq!(None, board.winning_line(Rules::default()));
+        assert!(board.mark(0));
+        assert_eq!(Some(Line::Row(0)), board.winning_line(Rules::default()));
+        assert_eq!(7, board.unmarked_sum());
+    }
+
+    #[test]
+    fn _diagonals() {
+        let (_, boards) = parse(EXAMPLE_INPUTS).unwrap();
+        let mut board = boards[0].clone();
+        for n in [22, 2, 14, 18] {
+            board.mark(n);
+        }
+        assert_eq!(None, board.winning_line(Rules { diagonals: true }));
+        board.mark(19);
+        assert_eq!(None, board.winning_line(Rules::default()));
+        assert_eq!(
+            Some(Line::Diagonal),
+            board.winning_line(Rules { diagonals: true })
+        );
+
+        let mut board = boards[2].clone();
+        for n in [4, 9, 23, 11, 2] {
+            board.mark(n);
+        }
+        assert_eq!(
+            Some(Line::AntiDiagonal),
+            board.winning_line(Rules { diagonals: true })
+        );
+
+        let (inputs, boards) = parse("1,4\n\n1 2\n3 4").unwrap();
+        let rules = Rules { diagonals: true };
+        let mut bingo = BingoSimulation::new(&inputs, boards).with_rules(rules);
+        let win = bingo.next().unwrap();
+        assert_eq!((Line::Diagonal, 20), (win.line, win.score));
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUTS.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(4_512, answer_part1(&parse(&input).unwrap()));
+    }
+}
@@ -0,0 +1,181 @@
+use common::{
+    parse::{lines, Line},
+    ParseError, Solution,
+};
+use std::cmp::Ordering;
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Vec2 = (usize, usize);
+
+/// Parses one line segment per line, each horizontal, vertical or at 45°.
+pub fn parse(s: &str) -> Result<Vec<(Vec2, Vec2)>, ParseError> {
+    lines(s)
+        .map(|l| {
+            let (start, end) = l.split_once(" -> ")?;
+            let (a, b) = (parse_point(&l, start)?, parse_point(&l, end)?);
+            if a.0 != b.0 && a.1 != b.1 && a.0.abs_diff(b.0) != a.1.abs_diff(b.1) {
+                return Err(l.error_at(end, "a point in line with the start or at 45° to it"));
+            }
+            Ok((a, b))
+        })
+        .collect()
+}
+
+fn parse_point(l: &Line, s: &str) -> Result<Vec2, ParseError> {
+    let (x, y) = s.split_once(',').ok_or_else(|| l.error_at(s, "`x,y`"))?;
+    Ok((l.parse(x, "a number")?, l.parse(y, "a number")?))
+}
+
+pub type Grid = common::Grid<u8>;
+
+pub fn draw(vents: &[(Vec2, Vec2)], diagonal: bool) -> Grid {
+    let (width, height) = dimensions(vents);
+    let mut grid = Grid::new(width, height, 0);
+
+    for (start, end) in vents {
+        if !diagonal && start.0 != end.0 && start.1 != end.1 {
+            continue;
+        }
+        let mut pos = *start;
+        grid[pos] += 1;
+        while pos != *end {
+            pos = (step(pos.0, end.0), step(pos.1, end.1));
+            grid[pos] += 1;
+        }
+    }
+    grid
+}
+
+/// One closer from `from` to `to`.
+fn step(from: usize, to: usize) -> usize {
+    match from.cmp(&to) {
+        Ordering::Less => from + 1,
+        Ordering::Equal => from,
+        Ordering::Greater => from - 1,
+    }
+}
+
+fn dimensions(vents: &[(Vec2, Vec2)]) -> (usize, usize) {
+    let (x_max, y_max) = vents
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .fold((0, 0), |(xm, ym), (x, y)| (xm.max(x), ym.max(y)));
+    (x_max + 1, y_max + 1)
+}
+
+/// The diagram from the puzzle text: `.` where no line passes, otherwise the number of lines.
+pub fn diagram(grid: &Grid) -> String {
+    grid.map(|&n| match n {
+        0 => ".".to_string(),
+        n => n.to_string(),
+    })
+    .to_string()
+}
+
+pub fn dangerous_vents(grid: &Grid) -> u32 {
+    grid.iter().filter(|&&n| n >= 2).count() as u32
+}
+
+pub fn answer_part1(vents: &[(Vec2, Vec2)]) -> u32 {
+    dangerous_vents(&draw(vents, false))
+}
+
+pub fn answer_part2(vents: &[(Vec2, Vec2)]) -> u32 {
+    dangerous_vents(&draw(vents, true))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<(Vec2, Vec2)>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn test_part1() {
+        let vents = parse(EXAMPLE_INPUT).unwrap();
+        let grid = draw(&vents, false);
+        assert_eq!(5, dangerous_vents(&grid));
+        assert_eq!(
+            ".......1..
+..1....1..
+..1....1..
+.......1..
+.112111211
+..........
+..........
+..........
+..........
+222111....
+",
+            diagram(&grid)
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let vents = parse(EXAMPLE_INPUT).unwrap();
+        let grid = draw(&vents, true);
+        assert_eq!(12, dangerous_vents(&grid));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 10, "` -> `")),
+            parse("0,9 -> 5,9\n8,0 - 0,8").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "a number")),
+            parse("-1,0 -> 3,0").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                8,
+                "a point in line with the start or at 45° to it"
+            )),
+            parse("0,0 -> 3,1").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(5, answer_part1(&parse(&input).unwrap()));
+    }
+}
@@ -0,0 +1,495 @@
+use common::{parse::lines, ParseError, Solution};
+use std::{error::Error, fmt, num::NonZeroU64};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+/// The timers of the fish: one that reaches 0 resets to `reset` and spawns a fish at `newborn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub reset: usize,
+    pub newborn: usize,
+}
+
+impl Cycle {
+    /// The cycle from the puzzle, a 7 day cycle with 2 extra days for newborns.
+    pub const LANTERNFISH: Cycle = Cycle {
+        reset: 6,
+        newborn: 8,
+    };
+
+    fn timers(&self) -> usize {
+        self.reset.max(self.newborn) + 1
+    }
+}
+
+/// A number of fish. Fixed width counts report overflow, big integers never overflow.
+pub trait Count: Clone + From<u64> {
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+impl Count for u64 {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+}
+
+impl Count for u128 {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u128::checked_add(*self, *other)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Count for num_bigint::BigUint {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+/// The population no longer fits the count type on `day`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub day: u64,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the population overflows on day {}", self.day)
+    }
+}
+
+impl Error for Overflow {}
+
+/// The number of fish per timer value, `day` days into the simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Model<N = u64> {
+    counts: Vec<N>,
+    cycle: Cycle,
+    day: u64,
+}
+
+impl Model {
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        Self::parse_with(s, Cycle::LANTERNFISH)
+    }
+
+    /// Parses the initial timers, which may be at most `cycle.reset`.
+    pub fn parse_with(s: &str, cycle: Cycle) -> Result<Self, ParseError> {
+        let expected = format!("a timer between 0 and {}", cycle.reset);
+        let mut counts = vec![0; cycle.timers()];
+        for l in lines(s) {
+            for n in l.text.split(',') {
+                let i = l.parse::<usize>(n, &expected)?;
+                if i > cycle.reset {
+                    return Err(l.error_at(n, expected));
+                }
+                counts[i] += 1;
+            }
+        }
+        Ok(Self {
+            counts,
+            cycle,
+            day: 0,
+        })
+    }
+
+    /// The matrix taking the counts of one day to the counts of the next.
+    fn transition(&self) -> Matrix {
+        let n = self.counts.len();
+        let mut m = Matrix::zero(n);
+        for i in 1..n {
+            m.0[i - 1][i] = 1;
+        }
+        m.0[self.cycle.reset][0] += 1;
+        m.0[self.cycle.newborn][0] += 1;
+        m
+    }
+
+    fn simulate_with(&self, days: u64, modulus: Option<u128>) -> Option<u128> {
+        let m = self.transition().pow(days, modulus)?;
+        let mut total = 0u128;
+        for row in &m.0 {
+            for (&a, &count) in row.iter().zip(&self.counts) {
+                total = add(total, mul(a, count as u128, modulus)?, modulus)?;
+            }
+        }
+        Some(total)
+    }
+
+    /// The number of fish after another `days` days, in time logarithmic in `days`.
+    ///
+    /// Fails once the population no longer fits a `u128`, use [`Model::simulate_mod`] or a
+    /// big integer [`Model::widen`] to go further.
+    pub fn simulate(&self, days: u64) -> Result<u128, Overflow> {
+        let mut counts: Vec<u128> = self.counts.iter().map(|&c| c as u128).collect();
+        if counts.iter().all(|&c| c == 0) {
+            return Ok(0);
+        }
+        // Powers of the transition overflow before the population does, so advance the counts
+        // by the largest powers that fit, checking the counts themselves. The population
+        // only grows, so once a power overflows it, smaller ones narrow down the day.
+        let mut powers = vec![self.transition()];
+        while 1u128 << powers.len() <= days as u128 {
+            let last = &powers[powers.len() - 1];
+            match last.mul(last, None) {
+                Some(next) => powers.push(next),
+                None => break,
+            }
+        }
+        let mut day = 0;
+        for (k, power) in powers.iter().enumerate().rev() {
+            while days - day >= 1 << k {
+                match power.apply(&counts) {
+                    Some(next) => counts = next,
+                    None => break,
+                }
+                day += 1 << k;
+            }
+        }
+        if day < days {
+            return Err(Overflow {
+                day: self.day + day + 1,
+            });
+        }
+        Ok(counts.iter().sum())
+    }
+
+    /// The number of fish after another `days` days, modulo `modulus`.
+    pub fn simulate_mod(&self, days: u64, modulus: NonZeroU64) -> u64 {
+        self.simulate_with(days, Some(modulus.get() as u128))
+            .expect("modular arithmetic can't overflow") as u64
+    }
+}
+
+impl<N: Count> Model<N> {
+    /// The same population with a wider count type.
+    pub fn widen<M: Count + From<N>>(&self) -> Model<M> {
+        Model {
+            counts: self.counts.iter().cloned().map(M::from).collect(),
+            cycle: self.cycle,
+            day: self.day,
+        }
+    }
+
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    /// Advances the model by one day, or leaves it unchanged if the population would overflow.
+    pub fn step(&mut self) -> Result<(), Overflow> {
+        let overflow = Overflow { day: self.day + 1 };
+        let mut counts = self.counts.clone();
+        counts.rotate_left(1);
+        let last = counts.len() - 1;
+        counts[last] = N::from(0);
+        let spawning = &self.counts[0];
+        for i in [self.cycle.reset, self.cycle.newborn] {
+            counts[i] = counts[i].checked_add(spawning).ok_or(overflow)?;
+        }
+        self.counts = counts;
+        self.day += 1;
+        Ok(())
+    }
+
+    pub fn run(&mut self, days: u64) -> Result<(), Overflow> {
+        for _ in 0..days {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    pub fn total(&self) -> Result<N, Overflow> {
+        self.counts.iter().try_fold(N::from(0), |total, n| {
+            total.checked_add(n).ok_or(Overflow { day: self.day })
+        })
+    }
+}
+
+fn add(a: u128, b: u128, modulus: Option<u128>) -> Option<u128> {
+    match modulus {
+        Some(m) => Some((a + b) % m),
+        None => a.checked_add(b),
+    }
+}
+
+fn mul(a: u128, b: u128, modulus: Option<u128>) -> Option<u128> {
+    match modulus {
+        Some(m) => Some((a * b) % m),
+        None => a.checked_mul(b),
+    }
+}
+
+/// A square matrix. With a modulus, entries stay below it, so they fit in a `u64` and
+/// products of two entries can't overflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Matrix(Vec<Vec<u128>>);
+
+impl Matrix {
+    fn zero(n: usize) -> Self {
+        Self(vec![vec![0; n]; n])
+    }
+
+    fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n);
+        for i in 0..n {
+            m.0[i][i] = 1;
+        }
+        m
+    }
+
+    fn reduce(mut self, modulus: Option<u128>) -> Matrix {
+        if let Some(m) = modulus {
+            self.0.iter_mut().flatten().for_each(|a| *a %= m);
+        }
+        self
+    }
+
+    fn mul(&self, other: &Matrix, modulus: Option<u128>) -> Option<Matrix> {
+        let n = self.0.len();
+        let mut out = Self::zero(n);
+        for i in 0..n {
+            for k in 0..n {
+                if self.0[i][k] == 0 {
+                    continue;
+                }
+                for j in 0..n {
+                    let product = mul(self.0[i][k], other.0[k][j], modulus)?;
+                    out.0[i][j] = add(out.0[i][j], product, modulus)?;
+                }
+            }
+        }
+        Some(out)
+    }
+
+    /// The matrix times `counts`, or `None` if an entry or their sum doesn't fit.
+    fn apply(&self, counts: &[u128]) -> Option<Vec<u128>> {
+        let out = self
+            .0
+            .iter()
+            .map(|row| {
+                row.iter().zip(counts).try_fold(0u128, |sum, (&a, &count)| {
+                    sum.checked_add(a.checked_mul(count)?)
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        out.iter().try_fold(0u128, |sum, &n| sum.checked_add(n))?;
+        Some(out)
+    }
+
+    fn pow(&self, mut exponent: u64, modulus: Option<u128>) -> Option<Matrix> {
+        let mut result = Self::identity(self.0.len()).reduce(modulus);
+        let mut base = self.clone().reduce(modulus);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&base, modulus)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.mul(&base, modulus)?;
+            }
+        }
+        Some(result)
+    }
+}
+
+pub fn answer_part1(model: &Model) -> u128 {
+    model.simulate(80).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn answer_part2(model: &Model) -> u128 {
+    model.simulate(256).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Model;
+    type Output1 = u128;
+    type Output2 = u128;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Model::parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "3,4,3,1,2";
+
+    #[test]
+    fn test_part1() {
+        let model = Model::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(5934, answer_part1(&model));
+    }
+
+    #[test]
+    fn test_part2() {
+        let model = Model::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(26984457539, answer_part2(&model));
+    }
+
+    #[test]
+    fn _step() {
+        let mut model = Model::parse(EXAMPLE_INPUT).unwrap();
+        for day in 1..=80 {
+            model.step().unwrap();
+            assert_eq!(
+                model.total().unwrap() as u128,
+                Model::parse(EXAMPLE_INPUT).unwrap().simulate(day).unwrap()
+            );
+        }
+        assert_eq!(80, model.day());
+        assert_eq!(5934, model.total().unwrap());
+    }
+
+    #[test]
+    fn _cycle() {
+        // Fish that split in two every day: 1, 2, 4, 8, ...
+        let doubling = Cycle {
+            reset: 0,
+            newborn: 0,
+        };
+        let model = Model::parse_with("0", doubling).unwrap();
+        assert_eq!(Ok(1 << 100), model.simulate(100));
+        assert_eq!(
+            Err(ParseError::new(1, 1, "a timer between 0 and 0")),
+            Model::parse_with("1", doubling).map(|_| ())
+        );
+
+        let slow = Cycle {
+            reset: 10,
+            newborn: 3,
+        };
+        let mut model = Model::parse_with("10,2", slow).unwrap();
+        model.run(50).unwrap();
+        assert_eq!(
+            model.total().unwrap() as u128,
+            Model::parse_with("10,2", slow)
+                .unwrap()
+                .simulate(50)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn _simulate_mod() {
+        const P: u64 = 1_000_000_007;
+        let p = NonZeroU64::new(P).unwrap();
+        let model = Model::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            (model.simulate(700).unwrap() % P as u128) as u64,
+            model.simulate_mod(700, p)
+        );
+
+        // Fish that split in two every day number 2^days, which is easy to check.
+        let doubling = Model::parse_with(
+            "0",
+            Cycle {
+                reset: 0,
+                newborn: 0,
+            },
+        )
+        .unwrap();
+        let days = 1_000_000_000_000u64;
+        let mut expected = 1u64;
+        let mut square = 2u64;
+        for bit in 0..u64::BITS - days.leading_zeros() {
+            if days >> bit & 1 == 1 {
+                expected = expected * square % P;
+            }
+            square = square * square % P;
+        }
+        assert_eq!(expected, doubling.simulate_mod(days, p));
+        assert_eq!(0, doubling.simulate_mod(days, NonZeroU64::MIN));
+    }
+
+    #[test]
+    fn _overflow() {
+        let doubling = Cycle {
+            reset: 0,
+            newborn: 0,
+        };
+        let mut model = Model::parse_with("0", doubling).unwrap();
+        model.run(63).unwrap();
+        assert_eq!(Ok(1 << 63), model.total());
+        assert_eq!(Err(Overflow { day: 64 }), model.step());
+        assert_eq!(63, model.day());
+
+        let model = Model::parse_with("0", doubling).unwrap();
+        assert_eq!(Err(Overflow { day: 128 }), model.simulate(200));
+        assert_eq!(
+            "the population overflows on day 128",
+            model.simulate(200).unwrap_err().to_string()
+        );
+
+        // Every timer fits, but their sum doesn't.
+        let model = Model {
+            counts: vec![u64::MAX, 1, 0, 0, 0, 0, 0, 0, 0],
+            cycle: Cycle::LANTERNFISH,
+            day: 5,
+        };
+        assert_eq!(Err(Overflow { day: 5 }), model.total());
+    }
+
+    #[test]
+    fn _simulate_far() {
+        // The powers of the transition overflow long before 10^12 days, the population never.
+        assert_eq!(Ok(0), Model::parse("").unwrap().simulate(1_000_000_000_000));
+
+        let model = Model::parse("3,4,3,1,2").unwrap();
+        let err = model.simulate(1_000_000_000_000).unwrap_err();
+        let mut stepped = model.widen::<u128>();
+        stepped.run(err.day - 1).unwrap();
+        assert_eq!(stepped.total(), model.simulate(err.day - 1));
+        assert!(stepped.step().and_then(|_| stepped.total()).is_err());
+        assert_eq!(Err(err), model.simulate(err.day));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn _bigint() {
+        use num_bigint::BigUint;
+
+        let model = Model::parse(EXAMPLE_INPUT).unwrap();
+        let overflow = model.simulate(2_000).unwrap_err();
+        let mut big = model.widen::<BigUint>();
+        big.run(2_000).unwrap();
+        assert!(big.total().unwrap() > BigUint::from(u128::MAX));
+        assert!(overflow.day < 2_000);
+
+        let mut model = model.widen::<u128>();
+        model.run(256).unwrap();
+        let mut big = Model::parse(EXAMPLE_INPUT).unwrap().widen::<BigUint>();
+        big.run(256).unwrap();
+        assert_eq!(BigUint::from(model.total().unwrap()), big.total().unwrap());
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(1, 5, "a timer between 0 and 6")),
+            Model::parse("3,4,9").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(5934, answer_part1(&Model::parse(&input).unwrap()));
+    }
+}
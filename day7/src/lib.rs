@@ -0,0 +1,491 @@
+use common::{parse::lines, ParseError, Solution};
+use std::{error::Error, fmt};
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub fn parse(s: &str) -> Result<Vec<u32>, ParseError> {
+    let mut poss = Vec::new();
+    for l in lines(s) {
+        poss.extend(l.parse_list::<u32>(',', "a number")?);
+    }
+    if poss.is_empty() {
+        return Err(ParseError::at_offset(s, s.len(), "a number"));
+    }
+    Ok(poss)
+}
+
+/// Where the crabs line up and how much fuel it takes them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alignment {
+    pub position: u32,
+    pub cost: u128,
+}
+
+/// The total fuel cost doesn't fit in a `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the fuel cost overflows a u128")
+    }
+}
+
+impl Error for Overflow {}
+
+/// How much fuel a crab burns to move some distance.
+pub trait FuelCost {
+    /// The fuel the crab at index `crab` burns to move `distance` steps, or `None` if it
+    /// doesn't fit in a `u128`.
+    fn cost(&self, crab: usize, distance: u32) -> Option<u128>;
+
+    /// Whether the total cost over all crabs is convex in the position they align at, which
+    /// holds whenever every crab's cost is convex in the distance.
+    fn is_convex(&self) -> bool;
+}
+
+/// 1 fuel per step, the puzzle's part 1.
+#[derive(Debug, Clone, Copy)]
+pub struct Linear;
+
+impl FuelCost for Linear {
+    fn cost(&self, _crab: usize, distance: u32) -> Option<u128> {
+        Some(distance as u128)
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+}
+
+/// Every step costs 1 more than the one before, the puzzle's part 2.
+#[derive(Debug, Clone, Copy)]
+pub struct Triangular;
+
+impl FuelCost for Triangular {
+    fn cost(&self, _crab: usize, distance: u32) -> Option<u128> {
+        Some(tri(distance) as u128)
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+}
+
+/// The square of the distance.
+#[derive(Debug, Clone, Copy)]
+pub struct Quadratic;
+
+impl FuelCost for Quadratic {
+    fn cost(&self, _crab: usize, distance: u32) -> Option<u128> {
+        Some(distance as u128 * distance as u128)
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+}
+
+/// Another cost that never exceeds `cap`.
+#[derive(Debug, Clone, Copy)]
+pub struct Capped<C> {
+    pub inner: C,
+    pub cap: u64,
+}
+
+impl<C: FuelCost> FuelCost for Capped<C> {
+    fn cost(&self, crab: usize, distance: u32) -> Option<u128> {
+        let cap = self.cap as u128;
+        Some(self.inner.cost(crab, distance).map_or(cap, |c| c.min(cap)))
+    }
+
+    fn is_convex(&self) -> bool {
+        false
+    }
+}
+
+/// There isn't exactly one weight per crab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeightCount {
+    pub weights: usize,
+    pub crabs: usize,
+}
+
+impl fmt::Display for WeightCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a weight for each of the {} crabs, got {}",
+            self.crabs, self.weights
+        )
+    }
+}
+
+impl Error for WeightCount {}
+
+/// Another cost multiplied by a weight per crab.
+#[derive(Debug, Clone)]
+pub struct Weighted<C> {
+    inner: C,
+    weights: Vec<u64>,
+}
+
+impl<C> Weighted<C> {
+    /// Weighs the crabs at `poss`, with `weights` indexed like them.
+    pub fn new(inner: C, weights: Vec<u64>, poss: &[u32]) -> Result<Self, WeightCount> {
+        if weights.len() != poss.len() {
+            return Err(WeightCount {
+                weights: weights.len(),
+                crabs: poss.len(),
+            });
+        }
+        Ok(Self { inner, weights })
+    }
+}
+
+impl<C: FuelCost> FuelCost for Weighted<C> {
+    fn cost(&self, crab: usize, distance: u32) -> Option<u128> {
+        (self.weights[crab] as u128).checked_mul(self.inner.cost(crab, distance)?)
+    }
+
+    fn is_convex(&self) -> bool {
+        self.inner.is_convex()
+    }
+}
+
+/// Any function of the distance, which isn't assumed to be convex.
+impl<F: Fn(u32) -> u64> FuelCost for F {
+    fn cost(&self, _crab: usize, distance: u32) -> Option<u128> {
+        Some(self(distance) as u128)
+    }
+
+    fn is_convex(&self) -> bool {
+        false
+    }
+}
+
+/// The fuel all crabs burn to line up at `position`, or `None` if it doesn't fit in a `u128`.
+///
+/// With at most 1 fuel per step, or `tri(distance) < 2^63` for the triangular cost, it fits
+/// for any number of crabs that fits in memory.
+pub fn total_cost(poss: &[u32], position: u32, fuel: &impl FuelCost) -> Option<u128> {
+    poss.iter().enumerate().try_fold(0u128, |sum, (i, &n)| {
+        sum.checked_add(fuel.cost(i, n.abs_diff(position))?)
+    })
+}
+
+/// How much the total cost changes from `position` to `position + 1`, or `None` if a crab's
+/// cost or the change doesn't fit. The change is usually much smaller than the total.
+fn slope(poss: &[u32], position: u32, fuel: &impl FuelCost) -> Option<i128> {
+    poss.iter().enumerate().try_fold(0i128, |sum, (i, &n)| {
+        let here = fuel.cost(i, n.abs_diff(position))?;
+        let next = fuel.cost(i, n.abs_diff(position + 1))?;
+        let change = if next >= here {
+            i128::try_from(next - here).ok()?
+        } else {
+            -i128::try_from(here - next).ok()?
+        };
+        sum.checked_add(change)
+    })
+}
+
+/// The cheapest position between the leftmost and the rightmost crab.
+///
+/// Convex costs are minimized with a binary search for where the slope stops being negative,
+/// which never needs the total cost away from the minimum, anything else by trying every
+/// position. Fails if the cheapest total doesn't fit in a `u128`.
+pub fn minimize(poss: &[u32], fuel: &impl FuelCost) -> Result<Alignment, Overflow> {
+    assert!(!poss.is_empty(), "requires 1 or more crabs");
+    let mut low = *poss.iter().min().unwrap();
+    let mut high = *poss.iter().max().unwrap();
+    if fuel.is_convex() {
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if slope(poss, mid, fuel).ok_or(Overflow)? >= 0 {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+    }
+    (low..=high)
+        .filter_map(|position| {
+            Some(Alignment {
+                position,
+                cost: total_cost(poss, position, fuel)?,
+            })
+        })
+        .min_by_key(|a| a.cost)
+        .ok_or(Overflow)
+}
+
+/// Aligns crabs that burn 1 fuel per step.
+///
+/// Moving the position right by one step costs 1 for every crab at or left of it and saves
+/// 1 for every crab right of it, so the cost stops falling once half the crabs are on the
+/// left: at the median.
+pub fn align_linear(poss: &[u32]) -> Alignment {
+    assert!(!poss.is_empty(), "requires 1 or more crabs");
+    let mut sorted = poss.to_vec();
+    let mid = (sorted.len() - 1) / 2;
+    let position = *sorted.select_nth_unstable(mid).1;
+    Alignment {
+        position,
+        cost: total_cost(poss, position, &Linear).expect("fits in a u128"),
+    }
+}
+
+/// Aligns crabs whose every step costs 1 more fuel than the one before.
+///
+/// The cost of moving `d` steps is `(d² + d) / 2`, so over all crabs the slope of the cost at
+/// position `p` is `n·(p - mean) ± n/2`. It is zero somewhere within half a step of the mean,
+/// and the cost is convex, so the best whole position is one of the integers around it.
+pub fn align_triangular(poss: &[u32]) -> Alignment {
+    assert!(!poss.is_empty(), "requires 1 or more crabs");
+    let n = poss.len() as u64;
+    let sum: u64 = poss.iter().map(|&p| p as u64).sum();
+    // floor(mean - 1/2) and ceil(mean + 1/2), in whole numbers.
+    let low = ((2 * sum).saturating_sub(n) / (2 * n)) as u32;
+    let high = (2 * sum + n).div_ceil(2 * n).min(u32::MAX as u64) as u32;
+    (low..=high)
+        .map(|position| Alignment {
+            position,
+            cost: total_cost(poss, position, &Triangular).expect("fits in a u128"),
+        })
+        .min_by_key(|a| a.cost)
+        .unwrap()
+}
+
+pub fn answer_part1(poss: &[u32]) -> u128 {
+    align_linear(poss).cost
+}
+
+pub fn answer_part2(poss: &[u32]) -> u128 {
+    align_triangular(poss).cost
+}
+
+pub fn tri(n: u32) -> u64 {
+    n as u64 * (n as u64 + 1) / 2
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<u32>;
+    type Output1 = u128;
+    type Output2 = u128;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_tri() {
+        assert_eq!(0, tri(0));
+        assert_eq!(1, tri(1));
+        assert_eq!(3, tri(2));
+        assert_eq!(6, tri(3));
+        assert_eq!(10, tri(4));
+        assert_eq!(15, tri(5));
+        assert_eq!(21, tri(6));
+        assert_eq!(9_223_372_034_707_292_160, tri(u32::MAX));
+    }
+
+    #[test]
+    fn test_part1() {
+        let positions = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(37, answer_part1(&positions));
+    }
+
+    #[test]
+    fn test_part2() {
+        let positions = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(168, answer_part2(&positions));
+    }
+
+    #[test]
+    fn _alignment() {
+        let positions = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            Alignment {
+                position: 2,
+                cost: 37
+            },
+            align_linear(&positions)
+        );
+        assert_eq!(
+            Alignment {
+                position: 5,
+                cost: 168
+            },
+            align_triangular(&positions)
+        );
+    }
+
+    /// Every position from the leftmost to the rightmost crab, the way the puzzle is phrased.
+    fn scan(poss: &[u32], fuel: &impl FuelCost) -> Option<u128> {
+        let min = *poss.iter().min().unwrap();
+        let max = *poss.iter().max().unwrap();
+        (min..=max).filter_map(|p| total_cost(poss, p, fuel)).min()
+    }
+
+    fn cost(alignment: Result<Alignment, Overflow>) -> Option<u128> {
+        alignment.ok().map(|a| a.cost)
+    }
+
+    /// Crabs from a small linear congruential generator, the same ones on every run.
+    fn crabs() -> impl Iterator<Item = Vec<u32>> {
+        let mut seed = 7u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            seed >> 16
+        };
+        (1..40).map(move |len| {
+            let range = next() % 200 + 1;
+            (0..len).map(|_| next() % range).collect()
+        })
+    }
+
+    #[test]
+    fn _matches_scan() {
+        for poss in crabs() {
+            assert_eq!(
+                scan(&poss, &Linear),
+                Some(align_linear(&poss).cost),
+                "{:?}",
+                poss
+            );
+            assert_eq!(
+                scan(&poss, &Triangular),
+                Some(align_triangular(&poss).cost),
+                "{:?}",
+                poss
+            );
+        }
+    }
+
+    #[test]
+    fn _minimize() {
+        for poss in crabs() {
+            let weights = (0..poss.len() as u64).map(|w| w % 5 + 1).collect();
+            let weighted = Weighted::new(Triangular, weights, &poss).unwrap();
+            let capped = Capped {
+                inner: Quadratic,
+                cap: 400,
+            };
+            assert_eq!(scan(&poss, &Linear), cost(minimize(&poss, &Linear)));
+            assert_eq!(scan(&poss, &Triangular), cost(minimize(&poss, &Triangular)));
+            assert_eq!(scan(&poss, &Quadratic), cost(minimize(&poss, &Quadratic)));
+            assert_eq!(scan(&poss, &weighted), cost(minimize(&poss, &weighted)));
+            assert_eq!(scan(&poss, &capped), cost(minimize(&poss, &capped)));
+        }
+
+        let positions = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Ok(align_linear(&positions)), minimize(&positions, &Linear));
+        assert_eq!(Some(168), cost(minimize(&positions, &|d: u32| tri(d))));
+        assert_eq!(
+            Err(WeightCount {
+                weights: 2,
+                crabs: 10
+            }),
+            Weighted::new(Linear, vec![1, 2], &positions).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _minimize_overflow() {
+        // The positions near the minimum fit in a u128, the left end doesn't.
+        let positions = [0, u32::MAX, u32::MAX];
+        let weighted = Weighted::new(Quadratic, vec![u64::MAX; 3], &positions).unwrap();
+        assert_eq!(None, total_cost(&positions, 0, &weighted));
+        let a = minimize(&positions, &weighted).unwrap();
+        assert_eq!(total_cost(&positions, a.position, &weighted), Some(a.cost));
+        assert!(total_cost(&positions, a.position - 1, &weighted) >= Some(a.cost));
+        assert!(total_cost(&positions, a.position + 1, &weighted) >= Some(a.cost));
+
+        // Even the cheapest position doesn't fit.
+        let positions = [[0; 3], [u32::MAX; 3]].concat();
+        let weighted = Weighted::new(Quadratic, vec![u64::MAX; 6], &positions).unwrap();
+        assert_eq!(Err(Overflow), minimize(&positions, &weighted));
+
+        // Weights that overflow on their own.
+        let heavier = Weighted::new(weighted, vec![u64::MAX; 6], &positions).unwrap();
+        assert_eq!(None, heavier.cost(0, u32::MAX));
+        assert_eq!(Err(Overflow), minimize(&positions, &heavier));
+    }
+
+    #[test]
+    fn _huge_coordinates() {
+        assert_eq!(u32::MAX, align_triangular(&[u32::MAX; 3]).position);
+
+        let positions = [0, 1_000_000_000, 3, 999_999_999, u32::MAX];
+        assert_eq!(
+            Alignment {
+                position: 999_999_999,
+                cost: 999_999_999 + 1 + 999_999_996 + (u32::MAX - 999_999_999) as u128
+            },
+            align_linear(&positions)
+        );
+        let a = align_triangular(&positions);
+        assert_eq!(
+            total_cost(&positions, a.position, &Triangular),
+            Some(a.cost)
+        );
+        assert!(total_cost(&positions, a.position - 1, &Triangular) >= Some(a.cost));
+        assert!(total_cost(&positions, a.position + 1, &Triangular) >= Some(a.cost));
+        assert_eq!(Ok(a), minimize(&positions, &Triangular));
+
+        // Far past a u64 at either end.
+        let positions = [[0; 5], [u32::MAX; 5]].concat();
+        assert_eq!(
+            Alignment {
+                position: 0,
+                cost: 5 * u32::MAX as u128
+            },
+            align_linear(&positions)
+        );
+        let a = align_triangular(&positions);
+        assert_eq!(u32::MAX / 2, a.position);
+        assert_eq!(
+            10 * tri(u32::MAX / 2) as u128 + 5 * (u32::MAX / 2 + 1) as u128,
+            a.cost
+        );
+        assert_eq!(Ok(a), minimize(&positions, &Triangular));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(1, 6, "a number")),
+            parse("16,1,a").map(|_| ())
+        );
+        assert_eq!(Err(ParseError::new(1, 1, "a number")), parse(""));
+        assert_eq!(Err(ParseError::new(3, 1, "a number")), parse(" \n\n"));
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(37, answer_part1(&parse(&input).unwrap()));
+    }
+}
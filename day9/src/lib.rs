@@ -0,0 +1,99 @@
+use common::{grid::Point, ParseError, Solution};
+use std::collections::HashSet;
+
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("inputs"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub type Grid = common::Grid<u8>;
+
+pub fn parse(s: &str) -> Result<Grid, ParseError> {
+    Grid::parse_digits(s)
+}
+
+pub fn low_points(g: &Grid) -> impl Iterator<Item = Point> + '_ {
+    g.points()
+        .filter(|&p| g.neighbours4(p).all(|n| g[p] < g[n]))
+}
+
+pub fn answer_part1(g: &Grid) -> u64 {
+    low_points(g).map(|p| g[p] as u64 + 1).sum()
+}
+
+pub fn answer_part2(g: &Grid) -> usize {
+    let mut products: Vec<_> = low_points(g)
+        .map(|p| {
+            let mut stack = vec![p];
+            let mut visited = HashSet::<Point>::new();
+
+            while let Some(p) = stack.pop() {
+                if !visited.insert(p) {
+                    continue;
+                }
+                stack.extend(g.neighbours4(p).filter(|&n| g[n] != 9 && g[n] > g[p]));
+            }
+            visited.len()
+        })
+        .collect();
+    products.sort();
+    products.into_iter().rev().take(3).product::<usize>()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Grid;
+    type Output1 = u64;
+    type Output2 = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        answer_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        answer_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn test_part1() {
+        let g = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(15, answer_part1(&g));
+    }
+    #[test]
+    fn test_part2() {
+        let g = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(1134, answer_part2(&g));
+    }
+
+    #[test]
+    fn _parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 2, "a digit")),
+            parse("219\n3a8").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn _untidy_input() {
+        let input = EXAMPLE_INPUT.replace('\n', " \r\n") + "\r\n\r\n";
+        assert_eq!(15, answer_part1(&parse(&input).unwrap()));
+    }
+}